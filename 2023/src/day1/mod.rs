@@ -1,4 +1,8 @@
-use std::{char, fs};
+use std::{char, fmt::Display};
+use aoc_utils::solution::Solution;
+
+
+pub struct Day1;
 
 
 
@@ -22,7 +26,7 @@ fn concat_to_digit(first: char, last: char) -> u32 {
 
 fn calibrate_values1(input: &str) -> u32 {
     input.lines()
-         .map(|line| concat_to_digit(first_digit(&line), last_digit(&line)))
+         .map(|line| concat_to_digit(first_digit(line), last_digit(line)))
          .sum()
 }
 
@@ -42,6 +46,28 @@ fn calibrate_values2(input: &str) -> u32 {
 }
 
 
+impl Solution for Day1 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 1;
+    // the same calibration document serves both parts
+    const INPUT: &'static str = "src/day1/input2.txt";
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display, String> {
+        Ok(calibrate_values1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display, String> {
+        Ok(calibrate_values2(input))
+    }
+}
+
+
 // fn calibrate_values3(input: &str) {
 //     let VALUE_REPLACEMENTS: Vec<(&str, i32)> = vec![("one", 1),
 //                                                     ("two", 2),
@@ -94,7 +120,7 @@ a1b2c3d4e5f
 treb7uchet";
 
     for line in input.lines() {
-        println!("{} => {:?} = {}", line, [first_digit(&line), last_digit(&line)], concat_to_digit(first_digit(&line), last_digit(&line)));
+        println!("{} => {:?} = {}", line, [first_digit(line), last_digit(line)], concat_to_digit(first_digit(line), last_digit(line)));
     }
 
     println!("total: {}", calibrate_values1(input));
@@ -103,7 +129,7 @@ treb7uchet";
 
 #[test]
 fn part1() {
-    let input = fs::read_to_string("src/input1.txt").unwrap();
+    let input = std::fs::read_to_string("src/day1/input1.txt").unwrap();
     println!("total: {}", calibrate_values1(input.as_str()));
 }
 
@@ -138,6 +164,6 @@ zoneight234
 
 #[test]
 fn part2() {
-    let input = fs::read_to_string("src/input2.txt").unwrap();
+    let input = std::fs::read_to_string("src/day1/input2.txt").unwrap();
     println!("total: {}", calibrate_values2(input.as_str()));
 }
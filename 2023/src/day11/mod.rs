@@ -0,0 +1,146 @@
+use std::{str::FromStr, fmt::{Display, Write}};
+use itertools::Itertools;
+use aoc_utils::{grid::Grid, position::Position, solution::Solution};
+
+
+const GALAXY: char = '#';
+
+
+pub struct Day11;
+
+
+#[derive(Debug)]
+struct Universe {
+    galaxies: Vec<Position>,
+    width: usize,
+    heigth: usize,
+}
+
+
+impl Universe {
+    // every empty row or column is replaced by `cosmic_expansion` empty rows or columns
+    fn expand(grid: &Grid<char>, cosmic_expansion: usize) -> Universe {
+        let found_galaxies = grid.find_all_positions(|c| *c == GALAXY);
+        let empty_rows: Vec<usize> = (0..grid.height).filter(|y| found_galaxies.iter().all(|g| g.y != *y)).collect();
+        let empty_columns: Vec<usize> = (0..grid.width).filter(|x| found_galaxies.iter().all(|g| g.x != *x)).collect();
+        let expand = |value: usize, empty: &[usize]| {
+            value + empty.iter().filter(|e| **e < value).count() * (cosmic_expansion - 1)
+        };
+
+        Universe {
+            galaxies: found_galaxies.iter()
+                                    .map(|g| Position { x: expand(g.x, &empty_columns), y: expand(g.y, &empty_rows) })
+                                    .collect(),
+            width: expand(grid.width, &empty_columns),
+            heigth: expand(grid.height, &empty_rows),
+        }
+    }
+}
+
+
+impl Display for Universe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.heigth {
+            for x in 0..self.width {
+                if self.galaxies.contains(&Position { x, y }) {
+                    f.write_char(GALAXY)?;
+                } else {
+                    f.write_char('.')?;
+                }
+            }
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+
+fn find_shortest_path(grid: &Grid<char>, cosmic_expansion: usize) -> u64 {
+    let universe = Universe::expand(grid, cosmic_expansion);
+    universe.galaxies.iter()
+                     .tuple_combinations()
+                     .map(|(g1, g2)| g1.manhattan_distance(g2) as u64)
+                     .sum()
+}
+
+
+impl Solution for Day11 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 11;
+    const INPUT: &'static str = "src/day11/input.txt";
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        Grid::from_str(input).map_err(|e| e.to_string())
+    }
+
+    fn part1(grid: &Self::Input) -> Result<impl Display, String> {
+        Ok(find_shortest_path(grid, 2))
+    }
+
+    fn part2(grid: &Self::Input) -> Result<impl Display, String> {
+        Ok(find_shortest_path(grid, 1000000))
+    }
+}
+
+
+#[test]
+fn example1() {
+    let input =
+"...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+    // rows or columns with no galaxies expand: 2x
+    // column: 3
+    // row: 2
+
+    // 5-9 = 9
+    // 1-7 = 15
+    // 3-6 = 17
+    // 8-9 = 5
+    assert_eq!(374, find_shortest_path(&Grid::from_str(input).unwrap(), 2));
+}
+
+
+#[test]
+fn part1() {
+    let input = std::fs::read_to_string("src/day11/input.txt").unwrap();
+    let result = find_shortest_path(&Grid::from_str(&input).unwrap(), 2);
+    println!("distance: \x1b[32m{}\x1b[0m", result);
+    assert_eq!(9648398, result);
+}
+
+
+#[test]
+fn example2() {
+    let input =
+"...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+    // rows or columns with no galaxies expand: 1000000
+    assert_eq!(82000210, find_shortest_path(&Grid::from_str(input).unwrap(), 1000000));
+}
+
+
+#[test]
+fn part2() {
+    let input = std::fs::read_to_string("src/day11/input.txt").unwrap();
+    let result = find_shortest_path(&Grid::from_str(&input).unwrap(), 1000000);
+    println!("distance: \x1b[32m{}\x1b[0m", result);
+    assert_eq!(618800410814, result);
+}
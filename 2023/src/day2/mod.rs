@@ -1,46 +1,48 @@
-use std::fs;
+use std::{fmt::Display, str::FromStr};
+use aoc_utils::solution::Solution;
 
-fn main() {
-    println!("Hello, world!");
-}
+
+pub struct Day2;
 
 #[derive(Debug, PartialEq, Eq)]
-struct Cubes {
+pub struct Cubes {
     pub red: i32,
     pub green: i32,
     pub blue: i32,
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     pub id: i32,
     pub revealed_subsets: Vec<Cubes>,
 }
 
-fn colored_cubes_count(color: &str, input: &str) -> i32 {
+fn colored_cubes_count(color: &str, input: &str) -> Result<i32, String> {
     if input.find(color).is_some() {
-        return input.split_whitespace().next().unwrap().parse::<i32>().unwrap();
+        let count = input.split_whitespace().next().ok_or(format!("missing count of {}", color))?;
+        return count.parse::<i32>().map_err(|_| format!("invalid count of {}: {}", color, count));
     }
-    return 0;
+    Ok(0)
 }
 
 
 
-impl From<&str> for Cubes {
+impl FromStr for Cubes {
+    type Err = String;
 
-    fn from(input: &str) -> Self {
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
         input.split(",")
-              .map(|colored_dice| (
-                colored_cubes_count("red", colored_dice),
-                colored_cubes_count("green", colored_dice),
-                colored_cubes_count("blue", colored_dice),
-              ))
-              .fold(Cubes { red: 0, green: 0, blue: 0,},
-                |accum, item| Cubes {
+              .map(|colored_dice| Ok((
+                colored_cubes_count("red", colored_dice)?,
+                colored_cubes_count("green", colored_dice)?,
+                colored_cubes_count("blue", colored_dice)?,
+              )))
+              .try_fold(Cubes { red: 0, green: 0, blue: 0,},
+                |accum, item: Result<(i32, i32, i32), String>| item.map(|item| Cubes {
                                                             red: accum.red + item.0, 
                                                             green: accum.green + item.1, 
                                                             blue: accum.blue + item.2,
-                                                        }
+                                                        })
               )
     }
 }
@@ -56,17 +58,21 @@ impl Cubes {
     }
 }
 
-impl From<&str> for Game {
-    fn from(line: &str) -> Self {
-        let (game_id, games) = line[5..].split_once(":").unwrap();
+impl FromStr for Game {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (game_id, games) = line.strip_prefix("Game ")
+                                   .and_then(|game| game.split_once(":"))
+                                   .ok_or(format!("invalid game: {}", line))?;
         let subsets = games.split(";")
-                                        .map(Cubes::from)
-                                        .collect::<Vec<Cubes>>();
+                                        .map(Cubes::from_str)
+                                        .collect::<Result<Vec<Cubes>, String>>()?;
 
-        Game {
-            id: game_id.parse::<i32>().unwrap(),
+        Ok(Game {
+            id: game_id.parse::<i32>().map_err(|_| format!("invalid game id: {}", game_id))?,
             revealed_subsets: subsets,
-        }
+        })
     }
 }
 
@@ -89,6 +95,33 @@ impl Game {
 }
 
 
+impl Solution for Day2 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 2;
+    const INPUT: &'static str = "src/day2/input.txt";
+
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        input.lines().map(Game::from_str).collect()
+    }
+
+    fn part1(games: &Self::Input) -> Result<impl Display, String> {
+        let bag_content = Cubes {red: 12, green: 13, blue: 14,};
+        Ok(games.iter()
+                .filter(|game| !game.is_not_possible(&bag_content))
+                .map(|game| game.id)
+                .sum::<i32>())
+    }
+
+    fn part2(games: &Self::Input) -> Result<impl Display, String> {
+        Ok(games.iter()
+                .map(|game| game.smallest_possible_cubes().power())
+                .sum::<i32>())
+    }
+}
+
+
 #[test]
 fn example1() {
     let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
@@ -100,7 +133,7 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"; // games
     let bag_content = Cubes {red: 12, green: 13, blue: 14,};
     let mut possible_games= 0;
 
-    for game in input.lines().map(Game::from).collect::<Vec<Game>>() {
+    for game in input.lines().map(|line| Game::from_str(line).unwrap()).collect::<Vec<Game>>() {
         println!("game {} is possible {}", game.id, game.is_not_possible(&bag_content));
         if !game.is_not_possible(&bag_content) {
             possible_games += game.id;
@@ -115,8 +148,8 @@ fn part1() {
     let bag_content = Cubes {red: 12, green: 13, blue: 14,};
     let mut possible_games= 0;
 
-    let input = fs::read_to_string("src/input.txt").unwrap();
-    for game in input.lines().map(Game::from).collect::<Vec<Game>>() {
+    let input = std::fs::read_to_string("src/day2/input.txt").unwrap();
+    for game in input.lines().map(|line| Game::from_str(line).unwrap()).collect::<Vec<Game>>() {
         println!("game {} is possible {}", game.id, game.is_not_possible(&bag_content));
         if !game.is_not_possible(&bag_content) {
             possible_games += game.id;
@@ -137,18 +170,16 @@ Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"; // games
 
-    let bag_content = Cubes {red: 12, green: 13, blue: 14,};
-    let mut possible_games= 0;
-    let games = input.lines().map(Game::from).collect::<Vec<Game>>();
+    let games = input.lines().map(|line| Game::from_str(line).unwrap()).collect::<Vec<Game>>();
 
     for game in &games {
         let smalles_cubes = game.smallest_possible_cubes();
         println!("game {} is possible with {:?} => {}", game.id, &smalles_cubes, smalles_cubes.power());
     }
 
-    assert_eq!(1, games.get(0).unwrap().id);
-    assert_eq!(Cubes{ red: 4, green: 2, blue: 6}, games.get(0).unwrap().smallest_possible_cubes());
-    assert_eq!(48, games.get(0).unwrap().smallest_possible_cubes().power());
+    assert_eq!(1, games.first().unwrap().id);
+    assert_eq!(Cubes{ red: 4, green: 2, blue: 6}, games.first().unwrap().smallest_possible_cubes());
+    assert_eq!(48, games.first().unwrap().smallest_possible_cubes().power());
     assert_eq!(2, games.get(1).unwrap().id);
     assert_eq!(Cubes{ red: 1, green: 3, blue: 4}, games.get(1).unwrap().smallest_possible_cubes());
     assert_eq!(12, games.get(1).unwrap().smallest_possible_cubes().power());
@@ -167,10 +198,10 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"; // games
 
 #[test]
 fn part2() {
-    let input = fs::read_to_string("src/input.txt").unwrap();
+    let input = std::fs::read_to_string("src/day2/input.txt").unwrap();
 
     let power_of_sets: i32 = input.lines()
-                                        .map(Game::from)
+                                        .map(|line| Game::from_str(line).unwrap())
                                         .map(|game| game.smallest_possible_cubes())
                                         .map(|gsc| gsc.power())
                                         .sum();
@@ -178,3 +209,10 @@ fn part2() {
     println!("sum of the power: {}", power_of_sets);
 }
 
+
+#[test]
+fn invalid_input() {
+    assert!(Day2::parse("garbage").is_err());
+    assert!(Day2::parse("Game x: 3 blue").is_err());
+    assert!(Day2::parse("Game 1: many red").is_err());
+}
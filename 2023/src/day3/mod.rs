@@ -0,0 +1,193 @@
+use std::{collections::HashMap, str::FromStr, fmt::Display};
+use aoc_utils::{grid::Grid, position::Position, solution::Solution};
+
+
+pub struct Day3;
+
+
+#[derive(Debug)]
+struct EnginePartNumber {
+    pub number: u32,
+    pub positions: Vec<Position>,
+}
+
+
+impl EnginePartNumber {
+    fn around(&self) -> Vec<Position> {
+        let mut around: Vec<Position> = self.positions.iter()
+                                                      .flat_map(|p| p.around())
+                                                      .filter(|p| !self.positions.contains(p))
+                                                      .collect();
+        around.sort();
+        around.dedup();
+        around
+    }
+}
+
+
+fn is_symbol(c: &char) -> bool {
+    *c != '.' && !c.is_ascii_digit()
+}
+
+
+fn find_numbers(grid: &Grid<char>) -> Vec<EnginePartNumber> {
+    let mut result = Vec::new();
+    let mut digits: Vec<Position> = Vec::new();
+
+    for position in grid.iter_lr_down() {
+        if position.x == 0 && !digits.is_empty() {
+            result.push(to_number(grid, &digits));
+            digits.clear();
+        }
+        if grid[position].is_ascii_digit() {
+            digits.push(position);
+        } else if !digits.is_empty() {
+            result.push(to_number(grid, &digits));
+            digits.clear();
+        }
+    }
+    if !digits.is_empty() {
+        result.push(to_number(grid, &digits));
+    }
+    result
+}
+
+
+fn to_number(grid: &Grid<char>, digits: &[Position]) -> EnginePartNumber {
+    let number = digits.iter()
+                       .fold(0, |nr, p| (nr * 10) + grid[*p].to_digit(10).unwrap_or_default());
+    EnginePartNumber { number, positions: digits.to_vec() }
+}
+
+
+fn find_numbers_with_adjecency(grid: &Grid<char>) -> Vec<(bool, u32)> {
+    find_numbers(grid).iter()
+                      .map(|nr| {
+                          let is_adjecent = nr.around().iter()
+                                                       .filter_map(|p| grid.at(p))
+                                                       .any(is_symbol);
+                          (is_adjecent, nr.number)
+                      })
+                      .collect()
+}
+
+
+fn find_gears(grid: &Grid<char>) -> Vec<u32> {
+    let mut gear_indicators: HashMap<Position, Vec<u32>> = HashMap::new();
+
+    for nr in find_numbers(grid) {
+        nr.around().into_iter()
+                   .filter(|p| grid.at(p) == Some(&'*'))
+                   .for_each(|p| gear_indicators.entry(p).or_default().push(nr.number));
+    }
+
+    gear_indicators.values()
+                   .filter(|digits| digits.len() == 2)
+                   .map(|digits| digits.iter().product())
+                   .collect()
+}
+
+
+
+impl Solution for Day3 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 3;
+    const INPUT: &'static str = "src/day3/input.txt";
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        Grid::from_str(input).map_err(|e| e.to_string())
+    }
+
+    fn part1(grid: &Self::Input) -> Result<impl Display, String> {
+        Ok(find_numbers_with_adjecency(grid).iter()
+                                            .filter(|(is_adjecent, _)| *is_adjecent)
+                                            .map(|(_, number)| number)
+                                            .sum::<u32>())
+    }
+
+    fn part2(grid: &Self::Input) -> Result<impl Display, String> {
+        Ok(find_gears(grid).iter().sum::<u32>())
+    }
+}
+
+
+#[test]
+fn example1() {
+
+    let input = 
+"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    let result = find_numbers_with_adjecency(&Grid::from_str(input).unwrap());
+
+    let non_adjecent: Vec<u32> = result.iter().filter(|val| !val.0).map(|val| val.1).collect();
+    let adjecent: Vec<u32> = result.iter().filter(|val| val.0).map(|val| val.1).collect();
+    let sum_adjecent: u32 = adjecent.iter().sum();
+
+    // println!("values: {:?}", &result);
+    assert_eq!(114, non_adjecent[0]);
+    assert_eq!(58, non_adjecent[1]);
+    assert_eq!(4361, sum_adjecent);
+}
+
+
+#[test]
+fn part1() {
+    let input = std::fs::read_to_string("src/day3/input.txt").unwrap();
+    let result = find_numbers_with_adjecency(&Grid::from_str(&input).unwrap());
+
+    let non_adjecent: Vec<u32> = result.iter().filter(|val| !val.0).map(|val| val.1).collect();
+    let adjecent: Vec<u32> = result.iter().filter(|val| val.0).map(|val| val.1).collect();
+    let sum_adjecent: u32 = adjecent.iter().sum();
+
+    println!("{:?}", non_adjecent);
+    println!("sum of all parts: {}", sum_adjecent);
+    assert_ne!(544359, sum_adjecent);
+    assert!(544359 < sum_adjecent);
+    assert_ne!(1134686, sum_adjecent);
+    assert!(1134686 > sum_adjecent);
+    assert_eq!(546563, sum_adjecent);
+}
+
+#[test]
+fn example2() {
+    let input = 
+"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    let result = find_gears(&Grid::from_str(input).unwrap());
+    let total: u32 = result.iter().sum();
+
+    println!("found gears: {:?}", result);
+    println!("total: {}", total);
+    assert_eq!(467835, total);
+}
+
+
+#[test]
+fn part2() {
+    let input = std::fs::read_to_string("src/day3/input.txt").unwrap();
+    let result = find_gears(&Grid::from_str(&input).unwrap());
+    let total: u32 = result.iter().sum();
+
+    println!("found gears: {:?}", result);
+    println!("total: {}", total);
+}
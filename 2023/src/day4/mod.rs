@@ -1,8 +1,12 @@
-use std::{fs, str::FromStr, num::ParseIntError, collections::BTreeMap};
+use std::{str::FromStr, collections::BTreeMap, fmt::Display};
+use aoc_utils::solution::Solution;
 
 
-#[derive(Debug, Eq, PartialEq, Hash, PartialOrd)]
-struct Card {
+pub struct Day4;
+
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Card {
     id: i32,
     winning_numbers: Vec<i32>,
     numbers_you_have: Vec<i32>,
@@ -10,24 +14,21 @@ struct Card {
 
 
 impl FromStr for Card {
-    type Err = ParseIntError;
+    type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (id_part, content_part) = s.split_once(":").unwrap();
-        let (winning_numbers_part, numbers_you_have_part) = content_part.split_once(" | ").unwrap();
+        let numbers = |part: &str| part.split_whitespace()
+                                       .map(|d| d.parse::<i32>().map_err(|_| format!("invalid number: {}", d)))
+                                       .collect::<Result<Vec<i32>, String>>();
+        let (id_part, content_part) = s.split_once(":").ok_or(format!("missing card id: {}", s))?;
+        let (winning_numbers_part, numbers_you_have_part) = content_part.split_once(" | ").ok_or(format!("missing numbers you have: {}", s))?;
         
-        let card_id = id_part.split_whitespace().last().unwrap().parse::<i32>();
-        let winnings = winning_numbers_part.split_whitespace()
-                                           .map(|d| d.parse::<i32>().unwrap())
-                                           .collect();
-        let numbers_you_have = numbers_you_have_part.split_whitespace()
-                                                    .map(|d| d.parse::<i32>().unwrap())
-                                                    .collect();
+        let card_id = id_part.split_whitespace().last().ok_or(format!("missing card id: {}", s))?;
         // "Card {id}: {list of nr} | {list of nr}";
         Ok(Card {
-            id: card_id.unwrap(),
-            winning_numbers: winnings,
-            numbers_you_have: numbers_you_have,
+            id: card_id.parse::<i32>().map_err(|_| format!("invalid card id: {}", card_id))?,
+            winning_numbers: numbers(winning_numbers_part)?,
+            numbers_you_have: numbers(numbers_you_have_part)?,
         })
     }
 }
@@ -40,6 +41,13 @@ impl Ord for Card {
 }
 
 
+impl PartialOrd for Card {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
 impl Card {
 
     fn matching(&self) -> impl Iterator<Item=&i32> + '_ {
@@ -55,23 +63,21 @@ impl Card {
                 points *= 2;
             }
         }
-        return points;
+        points
     }
 }
 
 
-fn scratchcards(input: &str) -> i32 {
-    let cards: Vec<Card> = input.lines().map(|line| Card::from_str(line).unwrap()).collect();
-    let mut carddeck: BTreeMap<Card, i32> = BTreeMap::from_iter(cards.into_iter().map(|card| (card, 1)));
+fn scratchcards(cards: &[Card]) -> i32 {
+    let mut carddeck: BTreeMap<Card, i32> = BTreeMap::from_iter(cards.iter().cloned().map(|card| (card, 1)));
     let mut total_scratchcards = 0;
 
     while carddeck.values().any(|cnt| *cnt > 0) {
         let mut card_iter = carddeck.iter_mut().skip_while(|e| *e.1 == 0);
 
         if let Some((current_card, current_card_count)) = card_iter.next() {
-            for _n in current_card.matching() {
-                // println!("current {:?} @ {:?} = + {}", &current_card, n, current_card_count);
-                let (_, next_card_count) = card_iter.next().unwrap();
+            // copies are won of the next cards only, never beyond the last card
+            for (_, next_card_count) in card_iter.take(current_card.matching().count()) {
                 *next_card_count += *current_card_count;
             }
             total_scratchcards += *current_card_count;
@@ -79,7 +85,28 @@ fn scratchcards(input: &str) -> i32 {
         }
     }
 
-    return total_scratchcards;
+    total_scratchcards
+}
+
+
+impl Solution for Day4 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 4;
+    const INPUT: &'static str = "src/day4/input.txt";
+
+    type Input = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        input.lines().map(Card::from_str).collect()
+    }
+
+    fn part1(cards: &Self::Input) -> Result<impl Display, String> {
+        Ok(cards.iter().map(|c| c.count_winnings()).sum::<i32>())
+    }
+
+    fn part2(cards: &Self::Input) -> Result<impl Display, String> {
+        Ok(scratchcards(cards))
+    }
 }
 
 
@@ -143,7 +170,7 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
 #[test]
 fn part1() {
-    let input = fs::read_to_string("src/input.txt").unwrap();
+    let input = std::fs::read_to_string("src/day4/input.txt").unwrap();
     let total_points: i32 = input.lines().map(|line| Card::from_str(line).unwrap()).map(|c| c.count_winnings()).sum();
     println!("total: {}", total_points);
 }
@@ -168,14 +195,21 @@ fn example2() {
     // card 6 (1x): 0 matching => copy of card: -
     // cards: 1 (1x), 2 (2x), 3 (4x), 4 (8x), 5 (14x), 6 (1x) = 30 cards
     // how many total scratchcards
-    let total_scratchcards = scratchcards(input);
+    let total_scratchcards = scratchcards(&Day4::parse(input).unwrap());
     println!("total cards: {}", total_scratchcards);
     assert_eq!(30, total_scratchcards);
 }
 
 #[test]
 fn part2() {
-    let input = fs::read_to_string("src/input.txt").unwrap();
-    let total_scratchcards = scratchcards(input.as_str());
+    let input = std::fs::read_to_string("src/day4/input.txt").unwrap();
+    let total_scratchcards = scratchcards(&Day4::parse(&input).unwrap());
     println!("total cards: {}", total_scratchcards);
 }
+
+
+#[test]
+fn invalid_input() {
+    assert!(Day4::parse("garbage").is_err());
+    assert!(Day4::parse("Card 1: 41 48 | 83 x").is_err());
+}
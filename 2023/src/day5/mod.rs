@@ -0,0 +1,244 @@
+use std::{str::FromStr, ops::Range, fmt::Display};
+use aoc_utils::{intervals::IntervalSet, solution::Solution};
+
+
+pub struct Day5;
+
+
+
+#[derive(Debug)]
+struct Conversion {
+    source_range_start: i64,
+    diff: i64,
+    length: i64,
+}
+
+
+
+impl FromStr for Conversion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s.split_whitespace()
+                      .map(|d| d.parse::<i64>().map_err(|_| format!("invalid number: {}", d)))
+                      .collect::<Result<Vec<i64>, String>>()?;
+        if values.len() != 3 {
+            return Err(format!("a conversion has a destination, source and length: {}", s));
+        }
+        Ok(Conversion {
+            diff: -(values[1] - values[0]),
+            source_range_start: values[1],
+            length: values[2],
+        })
+    }
+}
+
+
+
+impl Conversion {
+    #[inline]
+    fn end(&self) -> i64 {
+        self.source_range_start + self.length
+    }
+
+
+    fn map(&self, input: i64) -> Option<i64> {
+        if input < self.source_range_start || input >= self.end() {
+            return None;
+        }
+        Some( input + self.diff )
+    }
+
+
+    fn source(&self) -> Range<i64> {
+        self.source_range_start..self.end()
+    }
+}
+
+
+
+#[derive(Debug)]
+struct ConversionMap {
+    conversions: Vec<Conversion>,
+}
+
+
+
+impl ConversionMap {
+   fn map(&self, input: i64) -> i64 {
+        self.conversions.iter().filter_map(|c| c.map(input)).next().unwrap_or(input)
+   }
+
+   // every number is converted by the first conversion that holds it
+   fn map_range(&self, input: IntervalSet) -> IntervalSet {
+        let pieces: Vec<(Range<i64>, i64)> = self.conversions.iter().map(|c| (c.source(), c.diff)).collect();
+        input.map_offsets(&pieces)
+   }
+}
+
+
+
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<i64>,
+    mapping: Vec<ConversionMap>,
+}
+
+
+
+fn parse_almanac(input: &str) -> Result<Almanac, String> {
+    let mut seeds = Vec::new();
+    let mut mapping: Vec<ConversionMap> = Vec::new();
+
+    for line in input.lines() {
+        if let Some(values) = line.strip_prefix("seeds:") {
+            seeds = values.split_whitespace()
+                          .map(|c| c.parse::<i64>().map_err(|_| format!("invalid seed: {}", c)))
+                          .collect::<Result<Vec<i64>, String>>()?;
+        }
+        else if line.is_empty() {
+            // none
+        }
+        else if line.ends_with("map:") {
+            mapping.push(ConversionMap {
+                conversions: Vec::new(),
+            });
+        }
+        else if let Some(conversion_map) = mapping.last_mut() {
+            conversion_map.conversions.push(Conversion::from_str(line)?);
+        }
+        else {
+            return Err(format!("conversion outside a map: {}", line));
+        }
+    }
+
+    Ok(Almanac { seeds, mapping })
+}
+
+
+
+fn find_lowest_location_for_initial_seeds(almanac: &Almanac) -> Vec<(i64,i64)> {
+    let mut result = Vec::new();
+    for seed in almanac.seeds.iter().copied() {
+        let location = almanac.mapping.iter().fold(seed, |acc, el| el.map(acc));
+        result.push((seed, location));
+    }
+    
+    result
+}
+
+
+
+// the seeds are pairs of the start and length of a range
+fn find_lowest_location_for_initial_seed_range(almanac: &Almanac) -> Option<i64> {
+    let mut seeds = IntervalSet::new();
+    for range in almanac.seeds.chunks_exact(2) {
+        seeds.insert(range[0]..range[0] + range[1]);
+    }
+
+    let remappings = almanac.mapping.iter()
+           .fold(seeds,
+                |acc, conv| conv.map_range(acc)
+            );
+    remappings.min()
+}
+
+
+
+impl Solution for Day5 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 5;
+    const INPUT: &'static str = "src/day5/input.txt";
+
+    type Input = Almanac;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        parse_almanac(input)
+    }
+
+    fn part1(almanac: &Self::Input) -> Result<impl Display, String> {
+        find_lowest_location_for_initial_seeds(almanac).iter()
+                                                       .map(|c| c.1)
+                                                       .min()
+                                                       .ok_or(String::from("no seeds"))
+    }
+
+    fn part2(almanac: &Self::Input) -> Result<impl Display, String> {
+        if almanac.seeds.len() % 2 != 0 {
+            return Err(String::from("the seeds do not pair up into ranges"));
+        }
+        find_lowest_location_for_initial_seed_range(almanac).ok_or(String::from("no seeds"))
+    }
+}
+
+#[test]
+fn example1() {
+    // seeds: 79, 14, 55, 13
+    // 
+    // seed-to-soil map:
+    //   50 98 2
+    //   52 50 48
+    // =>
+    // format: destination range start, source range start, range length
+    // convert source > destination category
+    // so:
+    // seed 50 => soil 52   => +2
+    // seed 51 => soil 53   => +2
+    // ...
+    // seed 97 => soil 99   => +2
+    // seed 98 => soil 50   => -48
+    // seed 99 => soil 51   => -48
+    // unmapped?
+    // source  => destination
+
+    // find the lowest location number that corresponds to any of the initial seeds.
+    // example.txt results in:
+    // seed 79, soil 81, fertilizer 81, water 81, light 74, temp 78, humidity 78, location 82
+    // seed 14, soil 14, fertilizer 53, water 49, light 42, temp 42, humidity 43, location 43
+    // seed 55, soil 57, fertilizer 57, water 53, light 46, temp 42, humidity 82, location 86
+    // seed 13, soil 13, fertilizer 52, water 41, light 34, temp 34, humidity 35, location 35
+    // answer: 35
+
+    let seed_and_locations = find_lowest_location_for_initial_seeds(&parse_almanac(include_str!("example.txt")).unwrap());
+
+    let mut verify_outcome = seed_and_locations.iter();
+    assert_eq!(Some(&(79, 82)), verify_outcome.next());
+    assert_eq!(Some(&(14, 43)), verify_outcome.next());
+    assert_eq!(Some(&(55, 86)), verify_outcome.next());
+    assert_eq!(Some(&(13, 35)), verify_outcome.next());
+    println!("Lowest location number: {:?}", seed_and_locations.iter().map(|c|c.1).min());
+}
+
+
+#[test]
+fn part1() {
+    let input = std::fs::read_to_string("src/day5/input.txt").unwrap();
+    let seed_and_locations = find_lowest_location_for_initial_seeds(&parse_almanac(&input).unwrap());
+    println!("Lowest location number: {:?}", seed_and_locations.iter().map(|c|c.1).min());
+}
+
+
+#[test]
+fn example2() {
+    let seed_and_locations = find_lowest_location_for_initial_seed_range(&parse_almanac(include_str!("example.txt")).unwrap());
+    println!("Lowest location number: \x1b[32m{}\x1b[0m", seed_and_locations.unwrap());
+    assert_eq!(Some(46), seed_and_locations);
+}
+
+
+#[test]
+fn part2() {
+    let input = std::fs::read_to_string("src/day5/input.txt").unwrap();
+    let seed_and_locations = find_lowest_location_for_initial_seed_range(&parse_almanac(&input).unwrap());
+    println!("Lowest location number: \x1b[32m{}\x1b[0m", seed_and_locations.unwrap());
+    assert_eq!(Some(47909639), seed_and_locations);
+}
+
+
+#[test]
+fn invalid_input() {
+    assert!(Day5::parse("garbage").is_err());
+    assert!(Day5::parse("seeds: 79 x").is_err());
+    assert!(Day5::parse("seeds: 79\n\nseed-to-soil map:\n50 98").is_err());
+    assert!(Day5::part2(&Day5::parse("seeds: 79 14 55").unwrap()).is_err());
+}
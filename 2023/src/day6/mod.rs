@@ -0,0 +1,145 @@
+use std::fmt::Display;
+use aoc_utils::solution::Solution;
+
+
+pub struct Day6;
+
+
+#[derive(Debug)]
+struct Race {
+    time: i64,
+    distance: i64,
+}
+
+
+fn parse_input(input: &str) -> Result<Vec<Race>, String> {
+    let mut time : Vec<i64> = Vec::new();
+    let mut distance: Vec<i64> = Vec::new();
+
+    for line in input.lines() {
+        let values = line.split_whitespace().skip(1)
+                         .map(|c| c.parse::<i64>().map_err(|_| format!("invalid number: {}", c)))
+                         .collect::<Result<Vec<i64>, String>>()?;
+        if line.starts_with("Time:") {
+            time = values;
+        }
+        else if line.starts_with("Distance:") {
+            distance = values;
+        }
+    }
+
+    if time.len() != distance.len() {
+        return Err(format!("{} times for {} distances", time.len(), distance.len()));
+    }
+    Ok(time.iter().zip(distance.iter())
+        .map(|(t,d)| { Race { time: *t, distance: *d, }})
+        .collect())
+}
+
+
+fn parse_input2(input: &str) -> Result<Race, String> {
+    let mut time = 0;
+    let mut distance = 0;
+
+    for line in input.lines() {
+        let (_, digits) = line.split_once(":").ok_or(format!("missing values: {}", line))?;
+        let values = digits.replace(char::is_whitespace, "").parse::<i64>().map_err(|_| format!("invalid number: {}", digits))?;
+        if line.starts_with("Time:") {
+            time = values;
+        }
+        else if line.starts_with("Distance:") {
+            distance = values;
+        }
+    }
+
+    Ok(Race {
+        time,
+        distance,
+    })
+}
+
+
+fn calc_record(race: &Race) -> Vec<i64> {
+    let mut input = Vec::new();
+    for hold_button_ms in 1..race.time-1 {
+        if hold_button_ms * (race.time - hold_button_ms) > race.distance {
+            input.push(hold_button_ms);
+        }
+    }
+    input
+}
+
+
+impl Solution for Day6 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 6;
+    const INPUT: &'static str = "src/day6/input.txt";
+
+    // part 2 reads the numbers as one, ignoring the spaces between them
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        parse_input(input)?;
+        parse_input2(input)?;
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display, String> {
+        Ok(parse_input(input)?.iter().map(|r| calc_record(r).len()).product::<usize>())
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display, String> {
+        Ok(calc_record(&parse_input2(input)?).len())
+    }
+}
+
+
+#[test]
+fn example1() {
+    let input = 
+"Time:      7  15   30
+Distance:  9  40  200";
+
+    let races = parse_input(input).unwrap();
+    println!("{:?}", &races);
+    assert_eq!(4, calc_record(&races[0]).len());
+    assert_eq!(vec![2,3,4,5], calc_record(&races[0]));
+    assert_eq!(8, calc_record(&races[1]).len());
+    assert_eq!(9, calc_record(&races[2]).len());
+    assert_eq!(288, races.iter().map(|r| calc_record(r).len() as i64).product::<i64>());
+}
+
+
+#[test]
+fn part1() {
+    let input = include_str!("input.txt");
+    println!("total: \x1b[32m{}\x1b[0m", parse_input(input).unwrap().iter().map(|r| calc_record(r).len()).product::<usize>());
+}
+
+
+#[test]
+fn example2() {
+    let input = 
+"Time:      7  15   30
+Distance:  9  40  200";
+
+    let race = parse_input2(input).unwrap();
+    println!("{:?}", &race);
+    assert_eq!(71503, calc_record(&race).len());
+}
+
+
+#[test]
+fn part2() {
+    let input = include_str!("input.txt");
+    let race = parse_input2(input).unwrap();
+    println!("total: \x1b[32m{}\x1b[0m", calc_record(&race).len());
+}
+
+
+#[test]
+fn invalid_input() {
+    assert!(Day6::parse("garbage").is_err());
+    assert!(Day6::parse("Time: 7 x\nDistance: 9 40").is_err());
+    assert!(Day6::parse("Time: 7 15\nDistance: 9").is_err());
+}
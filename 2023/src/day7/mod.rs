@@ -1,15 +1,14 @@
-use std::{num::ParseIntError, collections::BTreeMap, cmp, fs};
-
-fn main() {
-    println!("Hello, world!");
-}
-
+use std::{collections::BTreeMap, cmp, fmt::Display};
+use aoc_utils::solution::Solution;
 
 const CARD: [char;13] = ['2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K','A'];
 const CARD2: [char;13] = ['J', '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'Q', 'K','A'];
 const JOKER: char = 'J';
 
 
+pub struct Day7;
+
+
 #[derive(Debug)]
 struct Hand<'a> {
     hand: &'a str,
@@ -18,13 +17,18 @@ struct Hand<'a> {
 
 
 impl<'a> TryFrom<&'a str> for Hand<'a> {
-    type Error = ParseIntError;
+    type Error = String;
 
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        let mut split = value.split_whitespace().into_iter();
+        let mut split = value.split_whitespace();
+        let hand = split.next().ok_or(String::from("missing hand"))?;
+        if hand.chars().count() != 5 || !hand.chars().all(|c| CARD.contains(&c)) {
+            return Err(format!("a hand has 5 cards: {}", hand));
+        }
+        let rank = split.next().ok_or(format!("missing bid: {}", value))?;
         Ok(Hand {
-            hand: split.next().unwrap().clone(),
-            rank: split.next().map(|d| d.parse::<i32>().unwrap()).unwrap(),
+            hand,
+            rank: rank.parse::<i32>().map_err(|_| format!("invalid bid: {}", rank))?,
         })
     }
 }
@@ -53,20 +57,19 @@ fn compare_part1(a: &Hand<'_>, b: &Hand<'_>) -> cmp::Ordering {
     match cmp {
         cmp::Ordering::Equal => a.hand.chars().zip(b.hand.chars())
                                             .map(|(a,b)| index_of_card(&a,&b))
-                                            .skip_while(|cmp| cmp.is_eq())
-                                            .next()
+                                            .find(|cmp| cmp.is_ne())
                                             .unwrap_or(cmp::Ordering::Equal),
         other => other,
     }
 }
 
 
-fn parse_card(input: &str) -> i32 {
+fn parse_card(input: &str) -> Result<i32, String> {
     let mut cards : Vec<Hand> = input.lines()
-                                .map(|line|Hand::try_from(line).unwrap())
-                                .collect();
+                                .map(Hand::try_from)
+                                .collect::<Result<Vec<Hand>, String>>()?;
     cards.sort_by(compare_part1);
-    (0..cards.len()).map(|n| cards.get(n).unwrap().rank * (1 + n as i32)).sum()
+    Ok((0..cards.len()).map(|n| cards[n].rank * (1 + n as i32)).sum())
 }
 
 
@@ -81,8 +84,7 @@ fn compare_part2(a: &Hand<'_>, b: &Hand<'_>) -> cmp::Ordering {
     match cmp {
         cmp::Ordering::Equal => a.hand.chars().zip(b.hand.chars())
                                             .map(|(a,b)| index_of_card2(&a,&b))
-                                            .skip_while(|cmp| cmp.is_eq())
-                                            .next()
+                                            .find(|cmp| cmp.is_ne())
                                             .unwrap_or(cmp::Ordering::Equal),
         other => other,
     }
@@ -102,16 +104,16 @@ fn card_recounted_for_joker(hand: &Hand) -> BTreeMap<char, i32> {
             card_counts.remove_entry(&JOKER);
         }
     }
-    return card_counts;
+    card_counts
 }
 
 
-fn parse_card2(input: &str) -> i32 {
+fn parse_card2(input: &str) -> Result<i32, String> {
     let mut cards : Vec<Hand> = input.lines()
-                                .map(|line|Hand::try_from(line).unwrap())
-                                .collect();
+                                .map(Hand::try_from)
+                                .collect::<Result<Vec<Hand>, String>>()?;
     cards.sort_by(compare_part2);
-    (0..cards.len()).map(|n| cards.get(n).unwrap().rank * (1 + n as i32)).sum()
+    Ok((0..cards.len()).map(|n| cards[n].rank * (1 + n as i32)).sum())
 }
 
 
@@ -129,6 +131,28 @@ fn index_of_card2(a: &char, b: &char) -> std::cmp::Ordering {
 }
 
 
+impl Solution for Day7 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 7;
+    const INPUT: &'static str = "src/day7/input.txt";
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        input.lines().try_for_each(|line| Hand::try_from(line).map(|_| ()))?;
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display, String> {
+        parse_card(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display, String> {
+        parse_card2(input)
+    }
+}
+
+
 #[test]
 fn example1() {
     let input =
@@ -143,16 +167,16 @@ QQQJA 483";
     // KK677 = 2 pair = 3               2*2+2*2+1*1 = 9
     // T55J5 = 3 kind = 4 // T.cmp(Q)   3*3+1*1+1*1 = 11
     // QQQJA = 3 kind = 5               3*3+1*1+1*1 = 11
-    println!("sum cards: \x1b[32m{}\x1b[0m", parse_card(input));
-    assert_eq!(6440, parse_card(input));
+    println!("sum cards: \x1b[32m{}\x1b[0m", parse_card(input).unwrap());
+    assert_eq!(6440, parse_card(input).unwrap());
 }
 
 
 #[test]
 fn part1() {
-    let input = fs::read_to_string("src/input.txt").unwrap();
-    println!("total winnings: \x1b[32m{}\x1b[0m", parse_card(input.as_str()));
-    assert_eq!(248569531, parse_card(input.as_str()));
+    let input = std::fs::read_to_string("src/day7/input.txt").unwrap();
+    println!("total winnings: \x1b[32m{}\x1b[0m", parse_card(input.as_str()).unwrap());
+    assert_eq!(248569531, parse_card(input.as_str()).unwrap());
     // < 249169463
 }
 
@@ -174,15 +198,23 @@ QQQJA 483";
     // T55J5 = 4 kind = 3                   4*4+1*1 = 17
     // QQQJA = 4 kind = 4 // Q.cmp(T)       4*4+1*1 = 17
     // KTJJT = 4 kind = 5 // K.cmp(Q)       4*4+1*1 = 17
-    println!("sum cards: \x1b[32m{}\x1b[0m", parse_card2(input));
-    assert_eq!(5905, parse_card2(input));
+    println!("sum cards: \x1b[32m{}\x1b[0m", parse_card2(input).unwrap());
+    assert_eq!(5905, parse_card2(input).unwrap());
 }
 
 
 #[test]
 fn part2() {
-    let input = fs::read_to_string("src/input.txt").unwrap();
-    println!("total winnings: \x1b[32m{}\x1b[0m", parse_card2(input.as_str()));
+    let input = std::fs::read_to_string("src/day7/input.txt").unwrap();
+    println!("total winnings: \x1b[32m{}\x1b[0m", parse_card2(input.as_str()).unwrap());
     // > 250146490
-    assert_eq!(250382098, parse_card2(input.as_str()));
+    assert_eq!(250382098, parse_card2(input.as_str()).unwrap());
+}
+
+
+#[test]
+fn invalid_input() {
+    assert!(Day7::parse("garbage").is_err());
+    assert!(Day7::parse("JJ 765").is_err());
+    assert!(Day7::parse("32T3K x").is_err());
 }
@@ -0,0 +1,218 @@
+use std::{collections::BTreeMap, str::Lines, fmt::Display};
+use aoc_utils::solution::Solution;
+
+
+pub struct Day8;
+
+
+type Network = BTreeMap<String, (String, String)>;
+// the node ids, and for every node the indexes of its left and right node
+type IndexedNetwork = (Vec<String>, Vec<(usize,usize)>);
+
+
+fn get_direction(direction: char, nodes: &(String,String)) -> &str {
+    if direction == 'L' {
+        return nodes.0.as_str();
+    }
+    nodes.1.as_str()
+}
+
+
+fn create_network_with_directions(input: &str) -> Result<(&str, Network), String> {
+    let mut lines = input.lines();
+    let directions = lines.next().unwrap_or_default();
+    if directions.is_empty() || !directions.chars().all(|c| c == 'L' || c == 'R') {
+        return Err(format!("invalid directions: {}", directions));
+    }
+    lines.next(); // skip blank line
+    Ok((directions, create_network(lines)?))
+}
+
+
+fn create_network(lines: Lines) -> Result<Network, String> {
+    let mut network: Network = BTreeMap::new();
+    for node in lines {
+        let n = node.split_once(" = ").ok_or(format!("invalid node: {}", node))?;
+        let node_id = n.0;
+        let connecting_nodes = n.1.replace("(", "").replace(")", "").clone();
+        let (node_l, node_r) = connecting_nodes.split_once(", ").ok_or(format!("invalid connections: {}", node))?;
+        network.insert(node_id.to_string(), (node_l.to_string(), node_r.to_string()));
+    }
+    Ok(network)
+}
+
+
+fn indexed_network(network: Network) -> Result<IndexedNetwork, String> {
+    let node_ids: Vec<String> = Vec::from_iter(network.keys().cloned());
+    let mut indexed_network = Vec::new();
+    let index_of = |id: &String| node_ids.iter().position(|x| x == id).ok_or(format!("unknown node: {}", id));
+    
+    for id in node_ids.iter() {
+        let (l, r) = &network[id];
+        indexed_network.push((index_of(l)?, index_of(r)?));
+    }
+
+    Ok((node_ids, indexed_network))
+}
+
+
+fn extract_indexes(ids2: &[String], end_value: &str) -> Vec<usize> {
+    ids2.iter()
+        .enumerate()
+        .filter(|x| x.1.ends_with(end_value))
+        .map(|x| x.0)
+        .collect()
+}
+
+
+fn calculate_steps(input: &str) -> Result<i32, String> {
+    let (dir, network) = create_network_with_directions(input)?;
+    let mut directions = dir.chars().cycle();
+
+    let mut steps = 0;
+    let mut current_node = "AAA".to_string();
+    loop {
+        let next_node = network.get(current_node.as_str()).ok_or(format!("unknown node: {}", current_node))?;
+        let dir = directions.next().unwrap();
+        let next = get_direction(dir, next_node);
+        if current_node == next || current_node == "ZZZ" {
+            break;
+        }
+        current_node = next.to_string();
+        steps += 1
+    }
+    Ok(steps)
+}
+
+
+fn calculate_steps2(input: &str) -> Result<i128, String> {
+    let (directions, network) = create_network_with_directions(input)?;
+    let (ids2, network2) = indexed_network(network)?;
+    let end_nodes = extract_indexes(&ids2, "Z");
+    let mut endings = Vec::new();
+
+    for n in extract_indexes(&ids2, "A") {
+        let mut steps = 0;
+        let mut next = n;
+        let mut directions_iter = directions.chars().cycle();
+        loop {
+            let dir = directions_iter.next().unwrap();
+            if end_nodes.contains(&next) {
+                break;
+            }
+            if dir == 'L' {
+                next = network2.get(next).unwrap().0;
+            } else {
+                next = network2.get(next).unwrap().1;
+            }
+            steps += 1;
+        }
+        endings.push(steps);
+    }
+
+    Ok(endings.iter().fold(1, |c, n| num::integer::lcm(c, *n)))
+}
+
+
+impl Solution for Day8 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 8;
+    const INPUT: &'static str = "src/day8/input.txt";
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        indexed_network(create_network_with_directions(input)?.1)?;
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display, String> {
+        calculate_steps(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display, String> {
+        calculate_steps2(input)
+    }
+}
+
+
+#[test]
+fn example1() {
+    let input =
+"RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+// AAA | 0 (1,2)
+// BBB | 1 (3,4)
+// CCC | 2 (6,5)
+// DDD | 3 (3,3)
+// EEE | 4 (4,4)
+// GGG | 5 (5,5)
+// ZZZ | 6 (6,6)
+    assert_eq!(2, calculate_steps(input).unwrap());
+}
+
+
+#[test]
+fn example2() {
+    let input =
+"LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+    assert_eq!(6, calculate_steps(input).unwrap());
+}
+
+
+#[test]
+fn part1() {
+    let input = std::fs::read_to_string("src/day8/input.txt").unwrap();
+    let result = calculate_steps(input.as_str()).unwrap();
+    println!("total steps: \x1b[32m{}\x1b[0m", result);
+    assert_ne!(13300, result);
+    assert_eq!(13301, result);
+}
+
+
+#[test]
+fn example3() {
+    let input =
+"LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+    assert_eq!(6, calculate_steps2(input).unwrap());
+}
+
+
+#[test]
+fn part2() {
+    let input = std::fs::read_to_string("src/day8/input.txt").unwrap();
+    let result = calculate_steps2(input.as_str()).unwrap();
+    println!("total steps: \x1b[32m{}\x1b[0m", result);
+    assert_ne!(12169, result); // groter
+    assert_ne!(13268366086992805522755101, result); // kleiner
+    assert_eq!(7309459565207, result);
+}
+
+
+#[test]
+fn invalid_input() {
+    assert!(Day8::parse("garbage").is_err());
+    assert!(Day8::parse("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)").is_err());
+    assert!(Day8::part1(&String::from("L\n\nBBB = (BBB, BBB)")).is_err());
+}
@@ -0,0 +1,109 @@
+use std::fmt::Display;
+use aoc_utils::solution::Solution;
+
+
+pub struct Day9;
+
+
+fn parse_histories(input: &str) -> Result<Vec<Vec<i64>>, String> {
+    input.lines()
+         .map(|line| {
+            let history = line.split_whitespace()
+                              .map(|d| d.parse::<i64>().map_err(|_| format!("invalid value: {}", d)))
+                              .collect::<Result<Vec<i64>, String>>()?;
+            if history.is_empty() {
+                return Err(String::from("empty history"));
+            }
+            Ok(history)
+         })
+         .collect()
+}
+
+
+fn calc(histories: &[Vec<i64>]) -> (i64, i64) {
+    let mut result = (0,0);
+    for history in histories {
+        let mut calc_stack = Vec::new();
+        calc_stack.push(history.clone());
+        
+        while !calc_stack.last().unwrap().iter().all(|d| *d == 0) {
+            let diff = calc_stack.last().unwrap().windows(2).map(|e| e[1]-e[0]).collect();
+            calc_stack.push(diff);
+        }
+
+        let extrapolated = calc_stack.iter().rfold((0, 0), |acc, e| (e.first().unwrap() - acc.0, acc.1 + e.last().unwrap()));
+        result.0 += extrapolated.0;
+        result.1 += extrapolated.1;
+
+        // for c in calc_stack {
+        //     print!("{:?} => ", &c);
+        // }
+        // println!("(before/after) {:?}", extrapolated);
+    }
+    result
+}
+
+
+
+impl Solution for Day9 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 9;
+    const INPUT: &'static str = "src/day9/input.txt";
+
+    type Input = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        parse_histories(input)
+    }
+
+    fn part1(histories: &Self::Input) -> Result<impl Display, String> {
+        Ok(calc(histories).1)
+    }
+
+    fn part2(histories: &Self::Input) -> Result<impl Display, String> {
+        Ok(calc(histories).0)
+    }
+}
+
+
+#[test]
+fn example1_2() {
+    let input =
+"0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    // 0 3 6 9 12 15 => 3 3 3 3 3 => 0 0 0 0
+    // B  <= A <= 0
+    // next result: 18 <= 3 <= 0
+    // previous result: -3 <= 3 <= 0 (part2)
+    // -3 and 18
+    // 1 3 6 10 15 21 => 2 3 4 5 6 => 1 1 1 1 => 0 0 0
+    // next result: 28 <= 7 <= 1 <= 0
+    // previous result: 0 <= 1 <= 1 <= 0 (part2)
+    // 0 and 28
+    // 10 13 16 21 30 45 => 3 3 5 9 15 => 0 2 4 6 => 2 2 2 => 0 0
+    // next result: 68 <= 23 <= 8 <= 2 <= 0
+    // previous result: 5 <= 5 <= -2 <= 2 <= 0 (part2)
+    // 5 and 68
+    // total before: 2
+    // total after: 114
+    assert_eq!((2, 114), calc(&parse_histories(input).unwrap()));
+}
+
+
+#[test]
+fn part1_2() {
+    let input = std::fs::read_to_string("src/day9/input.txt").unwrap();
+    let result = calc(&parse_histories(&input).unwrap());
+    println!("extrapolate before: \x1b[32m{}\x1b[0m", result.0);
+    println!("extrapolate after: \x1b[32m{}\x1b[0m", result.1);
+    assert_eq!((913, 1789635132), result);
+}
+
+
+#[test]
+fn invalid_input() {
+    assert!(Day9::parse("garbage").is_err());
+    assert!(Day9::parse("0 3 6\n\n1 3 6").is_err());
+}
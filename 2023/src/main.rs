@@ -0,0 +1,19 @@
+use std::process::ExitCode;
+
+pub mod runner;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day11;
+
+
+fn main() -> ExitCode {
+    aoc_utils::cli::main("aoc2023", runner::YEAR, runner::registry())
+}
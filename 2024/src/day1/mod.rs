@@ -1,32 +1,42 @@
-use std::fs;
+use std::fmt::Display;
+use aoc_utils::solution::Solution;
 
 
-pub fn parse_input_pair(input: &str) -> (Vec<u64>, Vec<u64>) {
+pub struct Day1;
+
+
+pub fn parse_input_pair(input: &str) -> Result<(Vec<u64>, Vec<u64>), String> {
     let mut left = Vec::<u64>::new();
     let mut right = Vec::<u64>::new();
 
-    input.lines()
-         .filter(|line| line.len() > 0)
-         .map(|line| line.split_whitespace())
-         .map(|mut line| (line.next().unwrap().parse().unwrap(), line.next().unwrap().parse().unwrap()))
-         .for_each(|(l, r): (u64, u64)| {
-            left.push(l);
-            right.push(r);
-         });
+    for line in input.lines().filter(|line| !line.is_empty()) {
+        let mut pair = line.split_whitespace().map(|nr| nr.parse::<u64>());
+        match (pair.next(), pair.next()) {
+            (Some(Ok(l)), Some(Ok(r))) => {
+                left.push(l);
+                right.push(r);
+            },
+            _ => return Err(format!("invalid location pair: {}", line)),
+        }
+    }
 
     left.sort();
     right.sort();
-    (left, right)
+    Ok((left, right))
+}
+
+
+fn difference(left: &[u64], right: &[u64]) -> u64 {
+    left.iter()
+        .zip(right.iter())
+        .map(|(&l, &r)| r.abs_diff(l))
+        .sum()
 }
 
 
 pub fn calc_difference(input: &str) -> u64 {
-    let (left, right) = parse_input_pair(input);
-    let total_diff = left.iter()
-                         .zip(right.iter())
-                         .map(|(&l, &r)| r.abs_diff(l))
-                         .sum();
-    total_diff
+    let (left, right) = parse_input_pair(input).unwrap();
+    difference(&left, &right)
 }
 
 
@@ -37,12 +47,37 @@ fn count_items(list: &Vec<u64>, item: u64) -> u64 {
 }
 
 
+fn similarity(left: &[u64], right: &Vec<u64>) -> u64 {
+    left.iter()
+        .map(|&l| l.checked_mul(count_items(right, l)).unwrap())
+        .sum()
+}
+
+
 pub fn calc_similarity(input: &str) -> u64 {
-    let (left, right) = parse_input_pair(input);
-    let total_diff = left.iter()
-                         .map(|&l| l.checked_mul(count_items(&right, l)).unwrap())
-                         .sum();
-    total_diff
+    let (left, right) = parse_input_pair(input).unwrap();
+    similarity(&left, &right)
+}
+
+
+impl Solution for Day1 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 1;
+    const INPUT: &'static str = "src/day1/input1.txt";
+
+    type Input = (Vec<u64>, Vec<u64>);
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        parse_input_pair(input)
+    }
+
+    fn part1((left, right): &Self::Input) -> Result<impl Display, String> {
+        Ok(difference(left, right))
+    }
+
+    fn part2((left, right): &Self::Input) -> Result<impl Display, String> {
+        Ok(similarity(left, right))
+    }
 }
 
 
@@ -65,15 +100,6 @@ fn example1() {
 }
 
 
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day1/input1.txt").unwrap();
-    let total_diff = calc_difference(&input);
-    println!("total diff: {}", total_diff);
-    assert_eq!(1506483, total_diff);
-}
-
-
 #[test]
 fn example2() {
     let input =
@@ -88,14 +114,4 @@ fn example2() {
     assert_eq!(31, total_diff);
 }
 
-
-
-#[test]
-fn part2() {
-    let input = fs::read_to_string("src/day1/input1.txt").unwrap();
-    let total_diff = calc_similarity(&input);
-    println!("total diff: {}", total_diff);
-    assert_eq!(23126924, total_diff);
 }
-
-}
\ No newline at end of file
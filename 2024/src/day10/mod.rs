@@ -1,89 +1,73 @@
-use std::{collections::HashSet, fs, str::FromStr};
+use std::{fmt::Display, str::FromStr};
 use std::collections::HashMap;
 use itertools::Itertools;
-use crate::utils::{grid::Grid, position::Position};
+use aoc_utils::dag::GridDag;
+use aoc_utils::solution::Solution;
+use aoc_utils::{grid::Grid, position::Position};
+
+
+pub struct Day10;
 
 
 const TRAILHEAD: char = '0';
-const TRAIL_FIRST_STEP: char = '1';
 const PEAK: char = '9';
 
 
+// a trail only goes up, one level at a time
+fn is_uphill(from: &char, to: &char) -> bool {
+    from.to_digit(10).zip(to.to_digit(10)).is_some_and(|(from, to)| to == from + 1)
+}
 
-fn create_trail_grid(input: &str) -> (Grid<char>, Vec<Position>) {
-    let grid = Grid::from_str(input).unwrap();
-    let trail_start_positions = grid.find_all_positions(|c| TRAILHEAD.eq(c));
-    (grid, trail_start_positions)
+
+fn is_peak(height: &char) -> bool {
+    PEAK.eq(height)
+}
+
+
+fn hiking_trails(grid: &Grid<char>) -> GridDag<'_, char> {
+    GridDag::new(grid, is_uphill).expect("uphill trails never lead back down")
 }
 
 
+/// The peaks reachable from every trailhead.
 fn find_trails(trail_start_positions: Vec<Position>, grid: Grid<char>) -> HashMap<Position, Vec<Position>> {
-    let mut trails = HashMap::new();
-    trail_start_positions.iter()
-                         .for_each(|trail_start| { trails.insert(trail_start.clone(), vec![trail_start.clone()]);});
-
-    for lvl in TRAIL_FIRST_STEP..=PEAK {
-        trails.iter_mut().for_each(|(_, lvls)| {
-            let mut next_steps : Vec<Position> = lvls.drain(..)
-                                        .flat_map(|p| p.sides())
-                                        .filter_map(|p| p)
-                                        .filter(|p| grid.at(p).eq(&Some(&lvl)))
-                                        .filter(|p| grid.in_grid(p))
-                                        .unique()
-                                        .collect();
-            lvls.append(&mut next_steps);
-        });
-    }
-    trails
+    let peaks = hiking_trails(&grid).reachable_sinks(is_peak);
+    trail_start_positions.into_iter()
+                         .map(|start| (start, peaks[start].iter().copied().sorted().collect()))
+                         .collect()
 }
 
 
-fn create_next_positions(grid: &Grid<char>, trail: &Vec<Position>, next_lvl: &char) -> Vec<Vec<Position>>
-{
-    trail.last().iter()
-            .flat_map(|l| l.sides())
-            .filter_map(|p| p)
-            .filter(move |p| grid.at(p).eq(&Some(next_lvl)))
-            .filter(|p| grid.in_grid(p))
-            .map(|next| {
-                let mut new_trail = trail.clone();
-                new_trail.push(next);
-                new_trail
-            })
-            .collect()
+/// The number of distinct trails from every trailhead to any peak.
+fn find_trails_rating(trail_start_positions: Vec<Position>, grid: Grid<char>) -> HashMap<Position, u64> {
+    let ratings = hiking_trails(&grid).path_counts(is_peak).expect("a trail of ten heights branches at most 4^9 ways");
+    trail_start_positions.into_iter()
+                         .map(|start| (start, ratings[start]))
+                         .collect()
 }
 
 
-fn find_trails_rating(trail_start_positions: Vec<Position>, grid: Grid<char>) -> HashMap<Position, usize> {
-    let mut trails: HashMap<Position, HashSet<Vec<Position>>> = HashMap::new();
-    trail_start_positions.iter()
-                         .map(|start_pos| (start_pos, HashSet::new()))
-                         .map(|mut trail| {
-                            trail.1.insert(vec![trail.0.clone()]);
-                            trail
-                        })
-                         .for_each(|(trail_start, trail)| {
-                            trails.insert(trail_start.clone(), trail);
-                        });
-
-    for lvl in TRAIL_FIRST_STEP..=PEAK {
-        for (_, trails_per_start) in trails.iter_mut() {
-            let trail_next: Vec<Vec<Position>> = 
-                    trails_per_start.drain()
-                                    .flat_map(|trail| {
-                                        let trails_for_next_step = create_next_positions(&grid, &trail, &lvl);
-                                        trails_for_next_step
-                                    })
-                                    .collect();
-            trail_next.iter().for_each(|t| {trails_per_start.insert(t.to_owned());});
-        }
+impl Solution for Day10 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 10;
+    const INPUT: &'static str = "src/day10/input.txt";
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        Grid::from_str(input).map_err(|e| e.to_string())
     }
 
-    let mut trail_ratings = HashMap::new();
-    trails.iter().for_each(|(start, unique_ways)| { trail_ratings.insert(*start, unique_ways.len());});
-    trail_ratings
-}
+    fn part1(grid: &Self::Input) -> Result<impl Display, String> {
+        let trails_start = grid.find_all_positions(|c| TRAILHEAD.eq(c));
+        Ok(find_trails(trails_start, grid.clone()).values().map(|t| t.len()).sum::<usize>())
+    }
 
+    fn part2(grid: &Self::Input) -> Result<impl Display, String> {
+        let trails_start = grid.find_all_positions(|c| TRAILHEAD.eq(c));
+        Ok(find_trails_rating(trails_start, grid.clone()).values().sum::<u64>())
+    }
+}
 
 
 #[cfg(test)]
@@ -91,6 +75,13 @@ pub mod day10_tests {
     use super::*;
 
 
+fn create_trail_grid(input: &str) -> (Grid<char>, Vec<Position>) {
+    let grid = Grid::from_str(input).unwrap();
+    let trail_start_positions = grid.find_all_positions(|c| TRAILHEAD.eq(c));
+    (grid, trail_start_positions)
+}
+
+
 #[test]
 fn example1() {
     let input = 
@@ -108,9 +99,6 @@ fn example1() {
     let (grid, trails_start) = create_trail_grid(input);
     let trails = find_trails(trails_start, grid);
 
-    println!("# of trails: {}", trails.len());
-    trails.iter().for_each(|(k,v)| println!("Trail @{k}={}  // {:?}", v.len(), v));
-    
     let total = trails.values().map(|t| t.len() as i32).sum();
     assert_eq!(5 + 6 + 5 + 3 + 1 + 3 + 5 + 3 + 5, total);
 }
@@ -133,17 +121,6 @@ fn example1_1() {
 }
 
 
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day10/input.txt").unwrap();
-    let (grid, trails_start) = create_trail_grid(&input);
-    let trails = find_trails(trails_start, grid);
-    let total = trails.values().map(|t| t.len() as i32).sum();
-    assert_ne!(632, total); // too high
-    assert_eq!(629, total);
-}
-
-
 #[test]
 fn example2_1() {
     let input = 
@@ -157,9 +134,6 @@ fn example2_1() {
     let (grid, trails_start) = create_trail_grid(input);
     let trails = find_trails_rating(trails_start, grid);
 
-    println!("# of trails: {}", trails.len());
-    trails.iter().for_each(|(k,v)| println!("Trail @{k}={}", v));
-    
     let total = trails.values().map(|t| *t as i32).sum();
     assert_eq!(3, total);
 }
@@ -179,9 +153,6 @@ fn example2_2() {
     let (grid, trails_start) = create_trail_grid(input);
     let trails = find_trails_rating(trails_start, grid);
 
-    println!("# of trails: {}", trails.len());
-    trails.iter().for_each(|(k,v)| println!("Trail @{k}={}", v));
-    
     let total = trails.values().map(|t| *t as i32).sum();
     assert_eq!(13, total);
 }
@@ -222,14 +193,34 @@ fn example2_4() {
 }
 
 
+
 #[test]
-fn part2() {
-    let input = fs::read_to_string("src/day10/input.txt").unwrap();
-    let (grid, trails_start) = create_trail_grid(&input);
-    let trails = find_trails_rating(trails_start, grid);
-    let total = trails.values().map(|t| *t as i32).sum();
-    assert_ne!(1292, total); // too high
-    assert_eq!(1242, total);
+fn lazy_trails() {
+    let input = 
+".....0.
+..4321.
+..5..2.
+..6543.
+..7..4.
+..8765.
+..9....";
+    let (grid, trails_start) = create_trail_grid(input);
+    let dag = hiking_trails(&grid);
+    let trails: Vec<Vec<Position>> = dag.paths(trails_start[0], is_peak).collect();
+    assert_eq!(3, trails.len());
+    assert!(trails.iter().all_unique());
+    assert!(trails.iter().all(|trail| trail.len() == 10 && trail.iter().map(|p| grid[*p]).eq(TRAILHEAD..=PEAK)));
+
+    let (grid, trails_start) = create_trail_grid(
+"012345
+123456
+234567
+345678
+4.6789
+56789.");
+    let dag = hiking_trails(&grid);
+    assert_eq!(227, dag.path_counts(is_peak).unwrap()[trails_start[0]]);
+    assert_eq!(5, dag.paths(trails_start[0], is_peak).take(5).count());
 }
 
 }
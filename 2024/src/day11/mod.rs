@@ -1,110 +1,77 @@
-use std::ops::Mul;
-use std::collections::HashMap;
+use std::fmt::Display;
+use aoc_utils::rewrite::count_after;
+use aoc_utils::solution::Solution;
 
 
-struct Cache {
-    cache: HashMap<(i64, i32), i64>,
-    cache_entries: i32,
-}
-
-
-impl Cache {
-    fn new() -> Self {
-        Cache { cache: HashMap::new(), cache_entries: 5 }
-    }
+pub struct Day11;
 
-    fn cache_for(&self, blink: i32) -> bool {
-        blink % self.cache_entries == 0
-    }
 
-    fn check_entry(&self, blink: i32, stone: i64) -> Option<&i64> {
-        if self.cache_for(blink) {
-            return self.cache.get(&(stone, blink));
-        }
-        None
+// 0 becomes 1, a stone with an even number of digits splits in two halves, any other is multiplied by 2024
+fn blink(stone: &i64) -> Vec<i64> {
+    if *stone == 0 {
+        return vec![1];
     }
-
-    fn cache_entry(&mut self, blink: i32, stone: i64, value: i64) {
-        if self.cache_for(blink) {
-            self.cache.insert((stone, blink), value);
-        }
-    }
-}
-
-
-fn split_on_blink(stone: i64) -> Vec<i64> {
     let num_of_digits = 1 + stone.ilog10();
-    if num_of_digits % 2 == 0 {
-        let split_by = 10_i64.pow((num_of_digits/2).try_into().unwrap());
+    if num_of_digits.is_multiple_of(2) {
+        let split_by = 10_i64.pow(num_of_digits / 2);
         vec![stone / split_by, stone % split_by]
     } else {
-        vec![stone.mul(2024)]
+        vec![stone * 2024]
     }
 }
 
 
-fn reshuffle_on_blink(blink: i32, line_of_stones: Vec<i64>) -> Vec<i64> {
-    if blink <= 0 {
-        return line_of_stones;
-    }
+// the order of the stones does not change their number, so equal stones are counted together
+fn count_stones_on_blink(blink_count: usize, line_of_stones: Vec<i64>) -> u64 {
+    count_after::<_, u64, _, _>(blink_count, line_of_stones, blink).total()
+}
 
-    let new_line_of_stones = line_of_stones.iter()
-                                                    .flat_map(|&digit| {
-                                                        if digit == 0 {
-                                                            return vec![1];
-                                                        }
-                                                        split_on_blink(digit)
-                                                    })
-                                                    .collect();
-    // println!(""After {blink} blinks remaining: {:?}", new_line_of_stones);
-    reshuffle_on_blink(blink - 1, new_line_of_stones)
+
+fn parse_stones(input: &str) -> Result<Vec<i64>, String> {
+    input.split_whitespace()
+         .map(|nr| nr.parse().map_err(|_| format!("invalid stone: {}", nr)))
+         .collect()
 }
 
 
-fn count_stones_on_blink_with_cache(blink: i32, line_of_stones: Vec<i64>, cache: &mut Cache) -> i64 {
-    let mut total = 0;
-    
-    if blink <= 0 {
-        return line_of_stones.len().try_into().unwrap();
-    }
+impl Solution for Day11 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 11;
+    const INPUT: &'static str = "src/day11/input.txt";
 
-    for stone in line_of_stones {
-        if let Some(cached_value) = cache.check_entry(blink, stone) {
-            total += cached_value;
-            continue;
-        }
-
-        let total_of_entry = if stone == 0 {
-                                    count_stones_on_blink_with_cache(blink - 1, vec![1], cache)
-                                  }
-                                  else if stone == 1 {
-                                    count_stones_on_blink_with_cache(blink - 1, vec![2024], cache)
-                                  }
-                                  else {
-                                    count_stones_on_blink_with_cache(blink - 1, split_on_blink(stone), cache)
-                                  };
-        cache.cache_entry(blink, stone, total_of_entry);
-        total += total_of_entry;
-    }
+    type Input = Vec<i64>;
 
-    total
-}
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        parse_stones(input)
+    }
 
+    fn part1(line_of_stones: &Self::Input) -> Result<impl Display, String> {
+        Ok(count_stones_on_blink(25, line_of_stones.clone()))
+    }
 
-fn count_stones_on_blink(blink: i32, mut line_of_stones: Vec<i64>) -> i64 {
-    let mut cache = Cache::new();
-    line_of_stones.sort();
-    count_stones_on_blink_with_cache(blink, line_of_stones, &mut cache)
+    fn part2(line_of_stones: &Self::Input) -> Result<impl Display, String> {
+        Ok(count_stones_on_blink(75, line_of_stones.clone()))
+    }
 }
 
 
-
 #[cfg(test)]
 pub mod day11_tests {
-    use std::fs;
     use super::*;
 
 
+fn reshuffle_on_blink(blink_count: i32, line_of_stones: Vec<i64>) -> Vec<i64> {
+    if blink_count <= 0 {
+        return line_of_stones;
+    }
+
+    let new_line_of_stones = line_of_stones.iter()
+                                           .flat_map(blink)
+                                           .collect();
+    reshuffle_on_blink(blink_count - 1, new_line_of_stones)
+}
+
+
 #[test]
 fn example1() {
     let input = "0 1 10 99 999";
@@ -118,7 +85,6 @@ fn example1() {
 }
 
 
-
 #[test]
 fn example1_2() {
     let input = "125 17";
@@ -133,30 +99,22 @@ fn example1_2() {
 
 
 #[test]
-fn example1_3() {
-    let input = "125 17";
-    let line_of_stones: Vec<i64> = input.split_whitespace().map(|nr| nr.parse().unwrap()).collect();
-    assert_eq!(55312, reshuffle_on_blink(25, line_of_stones.clone()).len());
-    assert_eq!(55312, count_stones_on_blink(25, line_of_stones));
-}
-
-
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day11/input.txt").unwrap();
-    let line_of_stones: Vec<i64> = input.split_whitespace().map(|nr| nr.parse().unwrap()).collect();
-    let total = reshuffle_on_blink(25, line_of_stones.clone()).len();
-    assert_eq!(182081, total);
-    assert_eq!(182081, count_stones_on_blink(25, line_of_stones));
+fn many_blinks() {
+    let line_of_stones = vec![125, 17];
+    assert_eq!(65601038650482, count_stones_on_blink(75, line_of_stones.clone()));
+    // the counts outgrow any integer, but the number of different stones stays small
+    let stones = count_after::<_, f64, _, _>(1000, line_of_stones, blink);
+    assert!(stones.total().is_finite() && stones.total() > 1e100);
+    assert!(stones.distinct() < 5000);
 }
 
 
 #[test]
-fn part2() {
-    let input = fs::read_to_string("src/day11/input.txt").unwrap();
+fn example1_3() {
+    let input = "125 17";
     let line_of_stones: Vec<i64> = input.split_whitespace().map(|nr| nr.parse().unwrap()).collect();
-    let total = count_stones_on_blink(75, line_of_stones);
-    assert_eq!(216318908621637, total);
+    assert_eq!(55312, reshuffle_on_blink(25, line_of_stones.clone()).len());
+    assert_eq!(55312, count_stones_on_blink(25, line_of_stones));
 }
 
 }
@@ -1,139 +1,65 @@
-use std::collections::BTreeSet;
-use std::{collections::HashSet, str::FromStr};
-use crate::utils::{grid::Grid, position::Position, Move};
-
-
-fn get_sorter(dir: &Move) -> impl FnMut(&&Position, &&Position) -> std::cmp::Ordering {
-    match dir {
-        Move::TOP   => |a: &&Position, b: &&Position| a.y.cmp(&b.y).then(a.x.cmp(&b.x)),
-        Move::DOWN  => |a: &&Position, b: &&Position| a.y.cmp(&b.y).then(a.x.cmp(&b.x)),
-        Move::RIGHT => |a: &&Position, b: &&Position| a.x.cmp(&b.x).then(a.y.cmp(&b.y)),
-        Move::LEFT  => |a: &&Position, b: &&Position| a.x.cmp(&b.x).then(a.y.cmp(&b.y)),
-        _ => todo!()
-    }
-}
+use std::collections::{BTreeSet, HashMap};
+use std::{fmt::Display, str::FromStr};
+use aoc_utils::grid::{render::Color, svg::Svg, Grid};
+use aoc_utils::regions::{regions, Connectivity};
+use aoc_utils::solution::Solution;
 
 
-fn is_adjecent(prev: &Position, next: &Position, facing_direction: &Move) -> bool {
-    match facing_direction {
-        Move::TOP   => prev.y == next.y && prev.x.abs_diff(next.x) == 1,
-        Move::DOWN  => prev.y == next.y && prev.x.abs_diff(next.x) == 1,
-        Move::RIGHT => prev.x == next.x && prev.y.abs_diff(next.y) == 1,
-        Move::LEFT  => prev.x == next.x && prev.y.abs_diff(next.y) == 1,
-        _ => todo!()
-    }
-}
+pub struct Day12;
 
 
-fn find_connected_with_same_value(grid: &Grid<char>, position: &Position) -> HashSet<Position> {
-    let mut result = HashSet::new();
-    let mut check_neighbours = BTreeSet::new();
-    let value = grid.at(position);
-    result.insert(position.clone());
-    check_neighbours.insert(position.clone());
-
-    
-    while let Some(pos) = check_neighbours.pop_first() {
-        for next_pos in pos.sides() {
-            if let Some(p) = next_pos {
-                if !result.contains(&p) &&
-                    grid.in_grid(&p) &&
-                    grid.at(&p).eq(&value) {
-                        check_neighbours.insert(p);
-                        result.insert(p);
-                }
-            }
-        }
-    }
-    result
+fn calculate_fencing_region(grid: &Grid<char>) -> usize {
+    regions(grid, Connectivity::FOUR).iter()
+                                     .map(|region| region.area() * region.perimeter())
+                                     .sum()
 }
 
 
-fn count_fence(region: &HashSet<Position>) -> usize {
-    let mut outer_limits = 0;
-    for pos in region {
-        for dir in [Move::TOP, Move::RIGHT, Move::DOWN, Move::LEFT] {
-            if pos.move_to(&dir).filter(|p| region.contains(p)).is_none() {
-                outer_limits += 1;
-            }
-        }
-    }
-    outer_limits
+fn calculate_fencing_region_bulk(grid: &Grid<char>) -> usize {
+    regions(grid, Connectivity::FOUR).iter()
+                                     .map(|region| region.area() * region.sides())
+                                     .sum()
 }
 
 
-fn count_fence_sides(region: &HashSet<Position>) -> usize {
-    let mut sum = 0;
-    for dir in [Move::TOP, Move::RIGHT, Move::DOWN, Move::LEFT] {
-        let match_fence_dir = get_edges_facing(region, &dir);
-        let fence_cnt = 
-        match_fence_dir.iter()
-                       .fold((None, 0), |res: (Option<&Position>, usize), cur| {
-                            if let Some(prev) = res.0 {
-                                if is_adjecent(prev, cur, &dir) {
-                                    return (Some(cur), res.1);
-                                }
-                            }
-                            (Some(cur), res.1 + 1)
-                       }).1;
-        sum += fence_cnt;
-    }
-
-    sum
+/// The garden with a fence around every region in the color of its plant, labelled with its plant, area and perimeter.
+pub fn draw_garden(grid: &Grid<char>) -> Svg {
+    let plants: BTreeSet<char> = grid.grid.iter().copied().collect();
+    let colors: HashMap<char, Color> = plants.iter().copied().zip(Color::palette(plants.len())).collect();
+    regions(grid, Connectivity::FOUR).iter()
+                                     .fold(grid.svg(|plant| colors[plant].blend(&Color::WHITE, 0.6)), |svg, region| {
+                                         let plant = grid[region.start()];
+                                         let text = format!("{} {}/{}", plant, region.area(), region.perimeter());
+                                         svg.region(region, colors[&plant]).label(&region.start(), &text)
+                                     })
 }
 
 
-fn get_edges_facing<'a>(region: &'a HashSet<Position>, facing_direction: &Move) -> Vec<&'a Position> {
-    let mut match_fence_dir = Vec::new();
-    for pos in region {
-        if pos.move_to(&facing_direction).filter(|p| region.contains(p)).is_none() {
-            match_fence_dir.push(pos);
-        }
-    }
-    match_fence_dir.sort_by(get_sorter(&facing_direction));
-    match_fence_dir
-}
+impl Solution for Day12 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 12;
+    const INPUT: &'static str = "src/day12/input.txt";
 
+    type Input = Grid<char>;
 
-fn calculate_fencing_region(grid: &Grid<char>) -> usize {
-    let mut visited: Vec<Position> = Vec::new();
-    let mut total = 0;
-
-    for position in grid.iter_lr_down() {
-        if visited.contains(&position) {
-            continue;
-        }
-        let connected_grid = find_connected_with_same_value(&grid, &position);
-        let fence_size = count_fence(&connected_grid);
-        connected_grid.iter().for_each(|&g| visited.push(g));
-        total += connected_grid.len() * fence_size;
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        Grid::from_str(input).map_err(|e| e.to_string())
     }
-    total
-}
 
+    fn part1(grid: &Self::Input) -> Result<impl Display, String> {
+        Ok(calculate_fencing_region(grid))
+    }
 
-fn calculate_fencing_region_bulk(grid: &Grid<char>) -> usize {
-    let mut visited: Vec<Position> = Vec::new();
-    let mut total = 0;
-
-    for position in grid.iter_lr_down() {
-        if visited.contains(&position) {
-            continue;
-        }
-        let connected_grid = find_connected_with_same_value(&grid, &position);
-        let fence_size = count_fence_sides(&connected_grid);
-        connected_grid.iter().for_each(|&g| visited.push(g));
-        total += connected_grid.len() * fence_size;
+    fn part2(grid: &Self::Input) -> Result<impl Display, String> {
+        Ok(calculate_fencing_region_bulk(grid))
     }
-    total
 }
 
 
-
 #[cfg(test)]
 pub mod day12_tests {
     use super::*;
-    use std::fs;
+    use std::collections::HashSet;
 
 
 #[test]
@@ -204,15 +130,18 @@ MMMISSJEEE";
     let grid = Grid::from_str(input).unwrap();
     let total = calculate_fencing_region(&grid);
     assert_eq!(1930, total);
-}
-
 
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day12/input.txt").unwrap();
-    let grid = Grid::from_str(&input).unwrap();
-    let total = calculate_fencing_region(&grid);
-    assert_eq!(1550156, total);
+    let garden = draw_garden(&grid);
+    garden.save("../target/day12/example1_3.svg").unwrap();
+    let garden = garden.to_string();
+    assert_eq!(11, garden.matches("<path").count());
+    // one fence color for each of the 9 plants, both regions of I and of C share theirs
+    let fences: HashSet<&str> = garden.lines()
+                                      .filter(|line| line.contains("<path"))
+                                      .filter_map(|line| line.split("stroke=\"").nth(1))
+                                      .collect();
+    assert_eq!(9, fences.len());
+    assert!(garden.contains(">R 12/18</text>") && garden.contains(">S 3/8</text>"));
 }
 
 
@@ -302,14 +231,4 @@ MMMISSJEEE";
     assert_eq!(1206, total);
 }
 
-
-#[test]
-fn part2() {
-    let input = fs::read_to_string("src/day12/input.txt").unwrap();
-    let grid = Grid::from_str(&input).unwrap();
-    let total = calculate_fencing_region_bulk(&grid);
-    assert_ne!(1550156, total);
-    assert_eq!(946084, total);
-}
-
 }
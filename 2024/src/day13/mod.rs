@@ -1,10 +1,28 @@
-use std::ops::Mul;
+use std::fmt::Display;
 use std::str::FromStr;
-use crate::utils::{position::Position};
+use aoc_utils::solution::Solution;
+use aoc_utils::{position::Position};
 
 
-#[derive(Debug)]
-struct Machine {
+pub struct Day13;
+
+
+const TOKENS_A: i128 = 3;
+const TOKENS_B: i128 = 1;
+const PRIZE_OFFSET: usize = 10000000000000;
+
+const OVERFLOW: &str = "overflow in 128-bit arithmetic";
+const NOT_ON_LINE: &str = "the buttons move along one line and the prize is not on it";
+const NOT_WHOLE: &str = "no whole number of presses reaches the prize";
+const NEGATIVE: &str = "the prize needs a negative number of presses";
+
+
+// the values of k for which a bound holds, None where a side is unbounded
+type Range = (Option<i128>, Option<i128>);
+
+
+#[derive(Debug, Clone)]
+pub struct Machine {
     id: usize,
     price_location: Position,
     move_a: Position,
@@ -23,139 +41,226 @@ impl FromStr for Machine {
         let mut btn_a = None;
         let mut bnt_b = None;
         let mut price = None;
-        let find_xy = |val: &str, split: &str| val.split(", ")
-                                                                          .map(|v| v.split_once(split).unwrap())
-                                                                          .map(|(dir, diff)| (dir, diff.parse().unwrap()))
-                                                                          .fold(Position::default(), |p, (xy, len)| {
-                                                                                match xy {
-                                                                                    "X" => Position { x: len, y: p.y },
-                                                                                    "Y" => Position { x: p.x, y: len },
-                                                                                    _ => p,
-                                                                                }
-                                                                          });
-
-        input.lines().take_while(|line| !line.is_empty())
-                     .map(|line| line.split_once(": ").unwrap())
-                     .for_each(|(cmd, val)| {
-                        match cmd {
-                            "Button A" => {
-                                btn_a = Some(find_xy(val, "+"));
-                            }
-                            "Button B" => {
-                                bnt_b = Some(find_xy(val, "+"));
-                            }
-                            "Prize" => {
-                                price = Some(find_xy(val, "="));
-                            }
-                            _ => {}
-                        }
-                     });
-
-        if price.is_none() {
-            return Err("unknown price");
-        }
-        if btn_a.is_none() {
-            return Err("unknown button A");
-        }
-        if bnt_b.is_none() {
-            return Err("unknown button B");
+        let find_xy = |val: &str, split: &str| -> Result<Position, &'static str> {
+            let mut position = Position::default();
+            for v in val.split(", ") {
+                let (xy, len) = v.split_once(split).ok_or("missing coordinate")?;
+                let len = len.parse().map_err(|_| "invalid coordinate")?;
+                match xy {
+                    "X" => position.x = len,
+                    "Y" => position.y = len,
+                    _ => {},
+                }
+            }
+            Ok(position)
+        };
+
+        for line in input.lines().take_while(|line| !line.is_empty()) {
+            let (cmd, val) = line.split_once(": ").ok_or("missing value")?;
+            match cmd {
+                "Button A" => {
+                    btn_a = Some(find_xy(val, "+")?);
+                }
+                "Button B" => {
+                    bnt_b = Some(find_xy(val, "+")?);
+                }
+                "Prize" => {
+                    price = Some(find_xy(val, "=")?);
+                }
+                _ => {}
+            }
         }
 
         Ok(Machine {
             id: 0,
-            price_location: price.unwrap(),
-            move_a: btn_a.unwrap(),
-            move_b: bnt_b.unwrap(),
+            price_location: price.ok_or("unknown price")?,
+            move_a: btn_a.ok_or("unknown button A")?,
+            move_b: bnt_b.ok_or("unknown button B")?,
         })
     }
 
 }
 
 
-fn create_machines(input: &str) -> Vec<Machine> {
-    let machines: Vec<Machine> = input.split("\n\n")
-                                      .enumerate()
-                                      .map(|(i, line)| Machine::from_str(line).and_then(|mut m| {m.id = i; Ok(m)}))
-                                      .map(|r| r.expect("Machine failed"))
-                                      .collect();
-    machines
-}
+impl Arcade {
 
-fn create_machines2(input: &str) -> Vec<Machine> {
-    let mut machines = create_machines(input);
-    for m in machines.iter_mut() {
-        m.price_location = Position{x: m.price_location.x + 10000000000000, y: m.price_location.y + 10000000000000};
+    fn calculate_total_tokens(self) -> usize {
+        self.machines.iter()
+                     .filter_map(|machine| machine.solve(Some(self.max_times as u64)).ok())
+                     .map(|presses| tokens(presses) as usize)
+                     .sum()
     }
-    machines
+
 }
 
 
-impl Arcade {
+fn tokens((add_a, add_b): (u64, u64)) -> u64 {
+    TOKENS_A as u64 * add_a + TOKENS_B as u64 * add_b
+}
 
-    fn calculate_total_tokens(self) -> usize {
-        let mut total = 0;
-        for machine in self.machines {
-            let max_range_a = machine.max_range_a(self.max_times);
-            let max_range_b = machine.max_range_b(self.max_times);
-            
-            if (max_range_a * machine.move_a.x + max_range_b * machine.move_b.x) < machine.price_location.x ||
-               (max_range_a * machine.move_a.y + max_range_b * machine.move_b.y) < machine.price_location.y
-            {
-                // println!("NOT POSSIBLE: {:?} BETWEEN A:{:?}, B:{:?}", machine, max_range_a, max_range_b);
-                continue;
-            } else {
-                if let Some((add_a, add_b)) = machine.find_move(max_range_a, max_range_b) {
-                    // println!("    POSSIBLE: {:?} == A:{:?}, B:{:?}", machine, add_a, add_b);
-                    total += 3 * add_a + 1 * add_b;
-                } else {
-                    // println!("NOT POSSIBLE: {:?} (BETWEEN A:{:?}, B:{:?})", machine, max_range_a, max_range_b);
-                }
-            }
-        }
-        total
+
+fn too_many(max: u64) -> String {
+    format!("the prize needs more than {} presses of a button", max)
+}
+
+
+// a.x * b.y - a.y * b.x
+fn cross(a: (i128, i128), b: (i128, i128)) -> Result<i128, String> {
+    let left = a.0.checked_mul(b.1).ok_or(OVERFLOW)?;
+    let right = a.1.checked_mul(b.0).ok_or(OVERFLOW)?;
+    Ok(left.checked_sub(right).ok_or(OVERFLOW)?)
+}
+
+
+// (g, x, y) with u * x + v * y = g = gcd(u, v)
+fn extended_gcd(u: i128, v: i128) -> (i128, i128, i128) {
+    if v == 0 {
+        return (u, 1, 0);
     }
-    
+    let (g, x, y) = extended_gcd(v, u % v);
+    (g, y, x - (u / v) * y)
 }
 
 
-impl Machine {
-    
-    fn max_range_a(&self, max: usize) -> usize {
-        max.min( self.price_location.x / self.move_a.x ).min( self.price_location.y / self.move_a.y )
+// the k for which lo <= c + k * d <= hi, None if there is none
+fn k_range(c: i128, d: i128, lo: i128, hi: Option<i128>) -> Option<Range> {
+    let ceil = |n: i128, d: i128| -(-n).div_euclid(d);
+    match d {
+        0 => (c >= lo && hi.is_none_or(|hi| c <= hi)).then_some((None, None)),
+        d if d > 0 => Some((Some(ceil(lo - c, d)), hi.map(|hi| (hi - c).div_euclid(d)))),
+        d => Some((hi.map(|hi| ceil(c - hi, -d)), Some((c - lo).div_euclid(-d)))),
     }
+}
 
-    fn max_range_b(&self, max: usize) -> usize {
-        max.min( self.price_location.x / self.move_b.x ).min( self.price_location.y / self.move_b.y )
+
+fn intersect(ranges: &[Option<Range>]) -> Option<Range> {
+    let mut result: Range = (None, None);
+    for range in ranges {
+        let (from, to) = (*range)?;
+        result = (result.0.max(from), match (result.1, to) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        });
+    }
+    match result {
+        (Some(from), Some(to)) if from > to => None,
+        _ => Some(result),
     }
+}
 
-    fn calc_location(&self, steps_a: usize, steps_b: usize) -> Position {
-        let new_x = self.move_a.x.mul(steps_a) + self.move_b.x.mul(steps_b);
-        let new_y = self.move_a.y.mul(steps_a) + self.move_b.y.mul(steps_b);
-        Position{x: new_x, y: new_y}
+
+// a * u + b * v = p with a, b >= 0 for the fewest tokens; u and v are not both 0
+fn solve_line(u: i128, v: i128, p: i128, max_presses: Option<u64>) -> Result<(i128, i128), String> {
+    let (g, x, y) = extended_gcd(u, v);
+    if p % g != 0 {
+        return Err(String::from(NOT_WHOLE));
     }
+    let a0 = x.checked_mul(p / g).ok_or(OVERFLOW)?;
+    let b0 = y.checked_mul(p / g).ok_or(OVERFLOW)?;
+
+    // all solutions are a = a0 + k * s, b = b0 - k * t
+    let (s, t) = (v / g, u / g);
+    let positive = intersect(&[k_range(a0, s, 0, None), k_range(b0, -t, 0, None)]).ok_or(NEGATIVE)?;
+    let max = max_presses.map(i128::from);
+    let (from, to) = intersect(&[Some(positive), k_range(a0, s, 0, max), k_range(b0, -t, 0, max)])
+                         .ok_or_else(|| too_many(max_presses.unwrap_or_default()))?;
+
+    // the cost changes linearly with k, so the cheapest is at one end; a, b >= 0 bound that end
+    let k = if TOKENS_A * s - TOKENS_B * t >= 0 { from } else { to }.ok_or(OVERFLOW)?;
+    let add_a = k.checked_mul(s).and_then(|ks| a0.checked_add(ks)).ok_or(OVERFLOW)?;
+    let add_b = k.checked_mul(t).and_then(|kt| b0.checked_sub(kt)).ok_or(OVERFLOW)?;
+    Ok((add_a, add_b))
+}
 
 
-    fn find_move(&self, max_a: usize, max_b: usize) -> Option<(usize, usize)> {
-        for btn_a_cnt in 0..=max_a {
-            for btn_b_cnt in 0..=max_b {
-                let calc_pos = self.calc_location(btn_a_cnt, btn_b_cnt);
-                match self.price_location.cmp(&calc_pos) {
-                    std::cmp::Ordering::Less => break,
-                    std::cmp::Ordering::Equal => return Some((btn_a_cnt, btn_b_cnt)),
-                    std::cmp::Ordering::Greater => continue,
-                }
+impl Machine {
+
+    fn far_away(&self) -> Machine {
+        Machine {
+            price_location: Position{x: self.price_location.x + PRIZE_OFFSET, y: self.price_location.y + PRIZE_OFFSET},
+            ..self.clone()
+        }
+    }
+
+    /// The presses of A and B that reach the prize for the fewest tokens, or why there are none.
+    fn solve(&self, max_presses: Option<u64>) -> Result<(u64, u64), String> {
+        let a = (self.move_a.x as i128, self.move_a.y as i128);
+        let b = (self.move_b.x as i128, self.move_b.y as i128);
+        let prize = (self.price_location.x as i128, self.price_location.y as i128);
+
+        let det = cross(a, b)?;
+        let (add_a, add_b) = if det != 0 {
+            // Cramer's rule: there is exactly one solution
+            let (num_a, num_b) = (cross(prize, b)?, cross(a, prize)?);
+            if num_a % det != 0 || num_b % det != 0 {
+                return Err(String::from(NOT_WHOLE));
             }
+            let (add_a, add_b) = (num_a / det, num_b / det);
+            if add_a < 0 || add_b < 0 {
+                return Err(String::from(NEGATIVE));
+            }
+            (add_a, add_b)
+        } else {
+            if cross(a, prize)? != 0 || cross(b, prize)? != 0 {
+                return Err(String::from(NOT_ON_LINE));
+            }
+            // the buttons are collinear, so one coordinate decides where the claw is on the line
+            if a.0 != 0 || b.0 != 0 {
+                solve_line(a.0, b.0, prize.0, max_presses)?
+            } else if a.1 != 0 || b.1 != 0 {
+                solve_line(a.1, b.1, prize.1, max_presses)?
+            } else if prize == (0, 0) {
+                (0, 0)
+            } else {
+                return Err(String::from(NOT_ON_LINE));
+            }
+        };
+
+        match max_presses {
+            Some(max) if add_a > max as i128 || add_b > max as i128 => Err(too_many(max)),
+            _ => Ok((add_a as u64, add_b as u64)),
         }
-        None
     }
+
 }
 
 
+impl Solution for Day13 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 13;
+    const INPUT: &'static str = "src/day13/input.txt";
+
+    type Input = Vec<Machine>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        input.split("\n\n")
+             .enumerate()
+             .map(|(i, block)| Machine::from_str(block).map(|mut m| {m.id = i; m}))
+             .collect::<Result<Vec<Machine>, &str>>()
+             .map_err(String::from)
+    }
+
+    fn part1(machines: &Self::Input) -> Result<impl Display, String> {
+        let arcade = Arcade {
+            max_times: 100,
+            machines: machines.clone(),
+        };
+        Ok(arcade.calculate_total_tokens())
+    }
+
+    fn part2(machines: &Self::Input) -> Result<impl Display, String> {
+        let arcade = Arcade {
+            max_times: usize::MAX,
+            machines: machines.iter().map(Machine::far_away).collect(),
+        };
+        Ok(arcade.calculate_total_tokens())
+    }
+}
+
 
 #[cfg(test)]
 pub mod day13_tests {
     use super::*;
-    use std::fs;
 
 
 #[test]
@@ -199,7 +304,7 @@ Prize: X=18641, Y=10279";
 
     let arcade = Arcade{
         max_times: 100,
-        machines: create_machines(&input),
+        machines: Day13::parse(input).unwrap(),
     };
     let total = arcade.calculate_total_tokens();
     assert_eq!(280 + 200, total);
@@ -208,55 +313,77 @@ Prize: X=18641, Y=10279";
 
 
 #[test]
-fn part1() {
-    let input = fs::read_to_string("src/day13/input.txt").unwrap();
+fn example2() {
+    let input = 
+"Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
+
+    let machines: Vec<Machine> = Day13::parse(input).unwrap().iter().map(Machine::far_away).collect();
+    assert_eq!(Err(String::from(NOT_WHOLE)), machines[0].solve(None));
+    assert!(machines[1].solve(None).is_ok());
+    assert_eq!(Err(String::from(NOT_WHOLE)), machines[2].solve(None));
+    assert!(machines[3].solve(None).is_ok());
+
     let arcade = Arcade{
-        max_times: 100,
-        machines: create_machines(&input),
+        max_times: usize::MAX,
+        machines,
     };
     let total = arcade.calculate_total_tokens();
-    assert_ne!(21456, total); // too low
-    assert_eq!(25751, total);
+    assert_eq!(875318608908, total);
 }
 
 
-// #[test]
-// fn example2() {
-//     let input = 
-// "Button A: X+94, Y+34
-// Button B: X+22, Y+67
-// Prize: X=8400, Y=5400
-
-// Button A: X+26, Y+66
-// Button B: X+67, Y+21
-// Prize: X=12748, Y=12176
-
-// Button A: X+17, Y+86
-// Button B: X+84, Y+37
-// Prize: X=7870, Y=6450
-
-// Button A: X+69, Y+23
-// Button B: X+27, Y+71
-// Prize: X=18641, Y=10279";
-
-//     let arcade = Arcade{
-//         max_times: 1000000000000000,
-//         machines: create_machines2(&input),
-//     };
-//     let total = arcade.calculate_total_tokens();
-//     assert_eq!(4800000000000000, total);
-// }
-
-// #[test]
-// // fn part2() {
-//     let input = fs::read_to_string("src/day13/input.txt").unwrap();
-//     let arcade = Arcade{
-//         max_times: 1000000000000000,
-//         machines: create_machines(&input),
-//     };
-//     let total = arcade.calculate_total_tokens();
-//     assert_ne!(21456, total); // too low
-//     assert_eq!(25751, total); ?
-// }
+#[test]
+fn collinear() {
+    let machine = |a: (usize, usize), b: (usize, usize), prize: (usize, usize)| Machine {
+        id: 0,
+        move_a: Position{x: a.0, y: a.1},
+        move_b: Position{x: b.0, y: b.1},
+        price_location: Position{x: prize.0, y: prize.1},
+    };
+
+    // A moves 4 times as far as B for 3 times the tokens: press A as often as possible
+    assert_eq!(Ok((4, 2)), machine((4, 8), (1, 2), (18, 36)).solve(None));
+    // A moves 2 times as far as B: press B only
+    assert_eq!(Ok((0, 9)), machine((2, 2), (1, 1), (9, 9)).solve(None));
+    assert_eq!(Ok((1, 3)), machine((6, 3), (4, 2), (18, 9)).solve(None));
+    assert_eq!(Err(too_many(2)), machine((6, 3), (4, 2), (18, 9)).solve(Some(2)));
+    assert_eq!(Err(String::from(NOT_ON_LINE)), machine((2, 2), (1, 1), (9, 8)).solve(None));
+    assert_eq!(Err(String::from(NOT_WHOLE)), machine((4, 4), (2, 2), (9, 9)).solve(None));
+    assert_eq!(Err(String::from(NEGATIVE)), machine((6, 3), (4, 2), (2, 1)).solve(None));
+    assert_eq!(Ok((0, 0)), machine((0, 0), (0, 0), (0, 0)).solve(None));
+    assert_eq!(Err(String::from(NOT_ON_LINE)), machine((0, 0), (0, 0), (1, 0)).solve(None));
+}
+
+
+#[test]
+fn no_solution() {
+    let machine = Machine { id: 0, move_a: Position{x: 3, y: 1}, move_b: Position{x: 1, y: 3}, price_location: Position{x: 0, y: 8} };
+    assert_eq!(Err(String::from(NEGATIVE)), machine.solve(None));
+    let machine = Machine { price_location: Position{x: 400, y: 400}, ..machine };
+    assert_eq!(Ok((100, 100)), machine.solve(Some(100)));
+    assert_eq!(Err(too_many(99)), machine.solve(Some(99)));
+}
+
+
+#[test]
+fn invalid_input() {
+    assert!(Day13::parse("garbage line here").is_err());
+    assert_eq!(Err("invalid coordinate"), Machine::from_str("Button A: X+a, Y+1\nButton B: X+1, Y+1\nPrize: X=1, Y=1").map(|_| ()));
+    assert_eq!(Err("unknown button B"), Machine::from_str("Button A: X+1, Y+1\nPrize: X=1, Y=1").map(|_| ()));
+}
 
 }
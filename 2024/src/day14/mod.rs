@@ -1,86 +1,191 @@
-use crate::utils::{position::Position};
-use std::{collections::HashMap};
+use aoc_utils::solution::Solution;
+use aoc_utils::grid::torus::Torus;
+use aoc_utils::point::Vector;
+use aoc_utils::{position::Position};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::str::FromStr;
 
 
-#[derive(Debug)]
-struct Robot {
+pub struct Day14;
+
+
+/// How much a frame looks like a picture, higher is more alike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Score {
+    /// Robots close together have a small variance of their positions per axis.
+    VARIANCE,
+    /// The size of the largest group of robots next to each other.
+    CLUSTER,
+    /// The number of distinct positions, a picture has no robots on top of each other.
+    DISTINCT,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Robot {
     start: Position,
-    velocity_x: i32,
-    velocity_y: i32,
+    velocity: Vector,
 }
 
 
 impl Robot {
 
-    fn move_n_within_grid(&self, n: i32, grid: &Position) -> Position {
-        let calc = |start: i64, velocity: i64, n: i64, grid: i64| (grid + (start + (velocity * n))).rem_euclid(grid);
-        let new_x = calc(self.start.x.try_into().unwrap(), self.velocity_x.into(), n.into(), grid.x.try_into().unwrap());
-        let new_y = calc(self.start.y.try_into().unwrap(), self.velocity_y.into(), n.into(), grid.y.try_into().unwrap());
-        Position {
-            x: usize::try_from(new_x).unwrap(),
-            y: usize::try_from(new_y).unwrap(),
+    fn position_after(&self, n: i64, area: &Torus) -> Position {
+        area.jump(&self.start, &self.velocity, n)
+    }
+
+}
+
+
+fn calc_sum_quadrants(robots: &[Robot], area: &Torus, n: i64) -> Result<usize, String> {
+    Ok(area.count_per_partition(robots.iter().map(|r| r.position_after(n, area)), 2, 2)?
+           .iter()
+           .product())
+}
+
+
+fn positions_after(robots: &[Robot], n: i64, area: &Torus) -> Vec<Position> {
+    robots.iter().map(|r| r.position_after(n, area)).collect()
+}
+
+
+fn variance<I>(values: I) -> f64
+    where I: Iterator<Item = usize> + Clone
+{
+    let count = values.clone().count() as f64;
+    let mean = values.clone().sum::<usize>() as f64 / count;
+    values.map(|value| (value as f64 - mean).powi(2)).sum::<f64>() / count
+}
+
+
+fn largest_cluster(positions: &[Position]) -> usize {
+    let mut unvisited: HashSet<Position> = positions.iter().cloned().collect();
+    let mut largest = 0;
+    while let Some(&start) = unvisited.iter().next() {
+        unvisited.remove(&start);
+        let mut todo = vec![start];
+        let mut size = 0;
+        while let Some(position) = todo.pop() {
+            size += 1;
+            for side in position.sides().into_iter().flatten() {
+                if unvisited.remove(&side) {
+                    todo.push(side);
+                }
+            }
+        }
+        largest = largest.max(size);
+    }
+    largest
+}
+
+
+impl Score {
+
+    fn of(&self, positions: &[Position]) -> f64 {
+        match self {
+            Score::VARIANCE => -variance(positions.iter().map(|p| p.x)) * variance(positions.iter().map(|p| p.y)),
+            Score::CLUSTER => largest_cluster(positions) as f64,
+            Score::DISTINCT => positions.iter().collect::<HashSet<_>>().len() as f64,
         }
     }
 
 }
 
 
-fn calc_sum_quadrants(robots: Vec<Robot>, gridsize: Position, n: i32) -> i32 {
-    let mut quadrants = HashMap::new();
-    for r in robots {
-        let new_pos = r.move_n_within_grid(n, &gridsize);
-        let quad = quadrant(&new_pos, &gridsize);
-        // println!("ROBOT: New {:?} {}", &new_pos, quad);
-        quadrants.entry(quad).and_modify(|cnt| *cnt += 1).or_insert(1);
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+
+// the t in 0..lcm(m, n) with t = a (mod m) and t = b (mod n), if there is one
+fn crt(a: i64, m: i64, b: i64, n: i64) -> Option<i64> {
+    let g = gcd(m, n);
+    if (b - a) % g != 0 {
+        return None;
     }
+    let lcm = m / g * n;
+    // m * k = b - a (mod n); m / g has an inverse modulo n / g
+    let (m_g, n_g) = (m / g, n / g);
+    let inverse = (1..=n_g).find(|i| (m_g * i) % n_g == 1 % n_g)?;
+    let k = ((b - a) / g * inverse).rem_euclid(n_g);
+    Some((a + m * k).rem_euclid(lcm))
+}
 
-    let total = quadrants.iter()
-                              .filter(|(k,_)| (0..4).contains(*k))
-                              .map(|(_,v)|*v)
-                              .product();
-    total
+
+/// The first step up to the lcm of the width and height, after which all positions repeat, with the best score.
+pub fn scan(robots: &[Robot], area: &Torus, score: Score) -> i64 {
+    let (width, height) = (area.width as i64, area.height as i64);
+    let period = width / gcd(width, height) * height;
+    let mut best = (f64::NEG_INFINITY, 0);
+    for step in 0..period {
+        let value = score.of(&positions_after(robots, step, area));
+        if value > best.0 {
+            best = (value, step);
+        }
+    }
+    best.1
 }
 
 
-fn create_robots(input: &str) -> Vec<Robot> {
-    let robots: Vec<Robot> = 
+/// The step where the robots draw a picture. The x positions repeat every `width` steps and the
+/// y positions every `height` steps, so each axis has its own step with the lowest variance;
+/// the picture appears where both coincide.
+pub fn find_easter_egg(robots: &[Robot], area: &Torus) -> Option<i64> {
+    let tightest = |size: usize, axis: fn(&Position) -> usize| {
+        (0..size as i64).map(|step| (variance(positions_after(robots, step, area).iter().map(axis)), step))
+                        .min_by(|a, b| a.0.total_cmp(&b.0))
+                        .map(|(_, step)| step)
+    };
+    let step_x = tightest(area.width, |p| p.x)?;
+    let step_y = tightest(area.height, |p| p.y)?;
+    crt(step_x, area.width as i64, step_y, area.height as i64)
+}
+
+
+impl FromStr for Robot {
+    type Err = &'static str;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (cur, vel) = line.split_once(" ").ok_or("missing velocity")?;
+        let (cur_x, cur_y) = cur.split_once("=").ok_or("missing position")?.1.split_once(",").ok_or("invalid position")?;
+        let (diff_x, diff_y) = vel.split_once("=").ok_or("missing velocity")?.1.split_once(",").ok_or("invalid velocity")?;
+
+        Ok(Robot {
+            start: Position{x: cur_x.parse().map_err(|_| "invalid position")?, y: cur_y.parse().map_err(|_| "invalid position")?},
+            velocity: Vector::new(diff_x.parse().map_err(|_| "invalid velocity")?, diff_y.parse().map_err(|_| "invalid velocity")?),
+        })
+    }
+}
+
+
+fn create_robots(input: &str) -> Result<Vec<Robot>, &'static str> {
     input.lines()
-         .map(|line| line.split_once(" ").unwrap())
-         .map(|(cur, vel)| {
-            let (cur_x, cur_y) = cur.split_once("=").unwrap().1.split_once(",").unwrap();
-            let (diff_x, diff_y) = vel.split_once("=").unwrap().1.split_once(",").unwrap();
-
-            Robot {
-                start: Position{x: cur_x.parse().unwrap(), y: cur_y.parse().unwrap()},
-                velocity_x: diff_x.parse().unwrap(),
-                velocity_y: diff_y.parse().unwrap(),
-            }
-         })
-         .collect();
-    robots
-}
-
-
-fn quadrant(position: &Position, grid: &Position) -> u8 {
-    // 0 | 1
-    // --+--  5
-    // 2 | 3
-    match (grid.x / 2).cmp(&position.x) {
-        std::cmp::Ordering::Less => {
-            match (grid.y / 2).cmp(&position.y) {
-                std::cmp::Ordering::Less => 3,
-                std::cmp::Ordering::Equal => 5,
-                std::cmp::Ordering::Greater => 1,
-            }
-        },
-        std::cmp::Ordering::Equal => 5,
-        std::cmp::Ordering::Greater => {
-            match (grid.y / 2).cmp(&position.y) {
-                std::cmp::Ordering::Less => 2,
-                std::cmp::Ordering::Equal => 5,
-                std::cmp::Ordering::Greater => 0,
-            }
-        },
+         .map(Robot::from_str)
+         .collect()
+}
+
+
+const AREA: Torus = Torus { width: 101, height: 103 };
+
+
+impl Solution for Day14 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 14;
+    const INPUT: &'static str = "src/day14/input.txt";
+
+    type Input = Vec<Robot>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        Ok(create_robots(input)?)
+    }
+
+    fn part1(robots: &Self::Input) -> Result<impl Display, String> {
+        calc_sum_quadrants(robots, &AREA, 100)
+    }
+
+    fn part2(robots: &Self::Input) -> Result<impl Display, String> {
+        find_easter_egg(robots, &AREA).ok_or(String::from("the steps of lowest variance per axis never coincide"))
     }
 }
 
@@ -89,12 +194,9 @@ fn quadrant(position: &Position, grid: &Position) -> u8 {
 pub mod day14_tests {
     use super::*;
     use std::fs;
-    use std::io::Write;
-    use crossterm::{QueueableCommand, terminal, cursor, style::{self, Stylize}};
-    use image::{Rgb, RgbImage};
+    use std::time::Duration;
+    use aoc_utils::grid::{recorder::Recorder, render::Color};
     
-const IDX_OF_TREE: i32 = 6577;
-const IMAGE_FOLDER: &str = "target/day14/";
 
 #[test]
 fn example1() {
@@ -113,61 +215,73 @@ p=7,3 v=-1,2
 p=2,4 v=2,-3
 p=9,5 v=-3,-3";
 
-    let robots = create_robots(input);
-    let gridsize = Position{x: 11, y: 7};
-    let total = calc_sum_quadrants(robots, gridsize, 100);
+    let robots = create_robots(input).unwrap();
+    let area = Torus::new(11, 7);
+    let total = calc_sum_quadrants(&robots, &area, 100).unwrap();
     assert_eq!(12, total);
 }
 
+
 #[test]
-fn part1() {
-    let input = fs::read_to_string("src/day14/input.txt").unwrap();
-    let robots = create_robots(&input);
-    let gridsize = Position{x: 101, y: 103};
-    let total = calc_sum_quadrants(robots, gridsize, 100);
-    assert_eq!(229421808, total);
+fn chinese_remainder() {
+    assert_eq!(Some(8), crt(2, 3, 3, 5));
+    assert_eq!(Some(6577), crt(6577 % 101, 101, 6577 % 103, 103));
+    assert_eq!(Some(10), crt(4, 6, 2, 8));
+    assert_eq!(None, crt(1, 6, 2, 8));
+}
+
+
+#[test]
+fn easter_egg() {
+    // a 3x4 block at step 0 that every robot leaves with its own speed
+    let area = Torus::new(31, 37);
+    let robots: Vec<Robot> = (0..12).map(|i| Robot {
+        start: Position{x: 10 + i % 3, y: 20 + i / 3},
+        velocity: Vector::new(i as i64 + 1, 2 * i as i64 - 11),
+    }).collect();
+    let at_step = |step: i64| robots.iter().map(|r| Robot { start: r.position_after(-step, &area), velocity: r.velocity }).collect::<Vec<_>>();
+
+    let later = at_step(40);
+    assert_eq!(Some(40), find_easter_egg(&later, &area));
+    assert_eq!(40, scan(&later, &area, Score::VARIANCE));
+    assert_eq!(40, scan(&later, &area, Score::CLUSTER));
 }
 
 
 #[test]
 fn part2() {
     let input = fs::read_to_string("src/day14/input.txt").unwrap();
-    let robots = create_robots(&input);
-    let gridsize = Position{x: 101, y: 103};
-    let mut stdout = std::io::stdout();
-
-    let robot_positions: Vec<Position> = robots.iter()
-                                                .map(|r| r.move_n_within_grid(IDX_OF_TREE, &gridsize))
-                                                .collect();
-
-    // save positions to file
-    match fs::exists(IMAGE_FOLDER) {
-        Ok(false) => fs::create_dir(IMAGE_FOLDER).unwrap(),
-        _ => {},
-    }
-    let mut img = RgbImage::from_pixel(gridsize.x.try_into().unwrap(), gridsize.y.try_into().unwrap(), Rgb([0, 0, 0]));
-    robot_positions.iter().for_each(|p| img.put_pixel(p.x.try_into().unwrap(), p.y.try_into().unwrap(), Rgb([31, 118, 5])));
-    img.save(format!("{}/img{}.png", IMAGE_FOLDER, IDX_OF_TREE)).unwrap();
-    
-    // show positions in console (shift content up 30 lines to show full image)
-    stdout.queue(terminal::Clear(terminal::ClearType::All)).unwrap()
-          .queue(cursor::MoveTo(110,0)).unwrap()
-          .queue(style::PrintStyledContent(format!("MAP {}", IDX_OF_TREE).magenta())).unwrap();
-    for y in 30..gridsize.y {
-        for x in 0..gridsize.x {
-            stdout.queue(cursor::MoveTo(x.try_into().unwrap(), (y - 30).try_into().unwrap())).unwrap();
-            if robot_positions.contains(&Position{x: x, y: y}) {
-                stdout.queue(style::PrintStyledContent("█".green())).unwrap();
-            } else {
-                stdout.queue(style::Print(" ")).unwrap();
-            }
-        }
+    let robots = create_robots(&input).unwrap();
+
+    let step = find_easter_egg(&robots, &AREA).unwrap();
+    assert_eq!(6577, step);
+    assert_eq!(step, scan(&robots, &AREA, Score::DISTINCT));
+}
+
+
+// writes ../target/day14/easter_egg.gif, run with `cargo test record_easter_egg -- --ignored`
+#[test]
+#[ignore]
+fn record_easter_egg() {
+    let input = fs::read_to_string("src/day14/input.txt").unwrap();
+    let robots = create_robots(&input).unwrap();
+    let step = find_easter_egg(&robots, &AREA).unwrap();
+
+    // replay the seconds before the picture appears
+    let mut recorder = Recorder::new(AREA.width, AREA.height).scale(4).delay(Duration::from_millis(200));
+    for n in step - 10..=step {
+        recorder.record_points(&positions_after(&robots, n, &AREA), Color::GREEN).unwrap();
     }
-    stdout
-        .queue(cursor::MoveToNextLine(1)).unwrap()
-        .flush().unwrap();
+    recorder.save_gif("../target/day14/easter_egg.gif").unwrap();
+    assert_eq!(11, recorder.len());
+}
 
-    assert_eq!(6577, IDX_OF_TREE);
+
+#[test]
+fn invalid_input() {
+    assert_eq!(Err("missing velocity"), create_robots("garbage").map(|_| ()));
+    assert_eq!(Err("invalid velocity"), create_robots("p=0,4 v=3,-3\np=6,3 v=-1;-3").map(|_| ()));
+    assert_eq!(Err("invalid position"), create_robots("p=-1,4 v=3,-3").map(|_| ()));
 }
 
 }
@@ -1,5 +1,15 @@
-use crate::utils::{grid::Grid, Move};
-use crate::utils::position::Position;
+use std::{fmt::Display, str::FromStr};
+use aoc_utils::solution::Solution;
+use aoc_utils::grid::render::Color;
+use aoc_utils::{grid::Grid, Move};
+use aoc_utils::position::Position;
+
+pub mod replay;
+
+use replay::{write, Change};
+
+
+pub struct Day15;
 
 
 struct Track {
@@ -8,10 +18,179 @@ struct Track {
 }
 
 
+fn execute_movements(movements: &str, warehouse: &mut Grid<char>, mut robot_position: Position) {
+    for mv in movements.chars() {
+        robot_position = execute_movement(mv, warehouse, robot_position);
+    }
+}
+
+
+// returns where the robot is after trying to move; anything but a movement is ignored
+fn execute_movement(mv: char, warehouse: &mut Grid<char>, robot_position: Position) -> Position {
+    execute_logged_movement(mv, warehouse, robot_position, &mut Vec::new())
+}
+
+
+// like `execute_movement`, and every written cell is added to the log
+fn execute_logged_movement(mv: char, warehouse: &mut Grid<char>, robot_position: Position, log: &mut Vec<Change>) -> Position {
+    Move::from_char(&mv).and_then(|robot_moving_direction| move_position(warehouse, &Track { value: '@', position: robot_position }, &robot_moving_direction, log))
+                        .unwrap_or(robot_position)
+}
+
+
+pub fn palette(c: &char) -> Color {
+    match c {
+        '#' => Color::GRAY,
+        'O' | '[' | ']' => Color::YELLOW,
+        '@' => Color::RED,
+        _ => Color::BLACK,
+    }
+}
+
+
+// a wide box is measured from its left edge
+fn goods_positioning_all(warehouse: &Grid<char>) -> usize {
+    warehouse.find_all_positions(|&c| c == 'O' || c == '[').iter()
+             .map(|good_position| goods_positioning_system(good_position))
+             .sum()
+}
+
+
+fn goods_positioning_system(item: &Position) -> usize {
+    100 * item.y + item.x
+}
+
+
+fn move_position(warehouse: &mut Grid<char>, item: &Track, mv: &Move, log: &mut Vec<Change>) -> Option<Position> {
+    if let Some(next_position) = item.position.move_to(mv) {
+        match warehouse.at(&next_position).unwrap() {
+            '.' => {
+                write(warehouse, &item.position, '.', log);
+                write(warehouse, &next_position, item.value, log);
+                return Some(next_position);
+            },
+            '#' => {
+                // cannot move
+            },
+            'O' => {
+                // try and move box
+                if move_position(warehouse, &Track { value: 'O', position: next_position }, mv, log).is_some() {
+                    write(warehouse, &item.position, '.', log);
+                    write(warehouse, &next_position, item.value, log);
+                    return Some(next_position);    
+                }
+            }, 
+            _ => {}
+        }
+    }
+    None
+}
+
+
+fn widen(warehouse: &Grid<char>) -> Grid<char> {
+    let grid = warehouse.grid.iter()
+                        .flat_map(|c| match c {
+                            '#' => ['#', '#'],
+                            'O' => ['[', ']'],
+                            '@' => ['@', '.'],
+                            _ => ['.', '.'],
+                        })
+                        .collect();
+    Grid { grid, width: warehouse.width * 2, height: warehouse.height }
+}
+
+
+fn execute_wide_movements(movements: &str, warehouse: &mut Grid<char>, mut robot_position: Position) {
+    for mv in movements.chars() {
+        robot_position = execute_wide_movement(mv, warehouse, robot_position);
+    }
+}
+
+
+fn execute_wide_movement(mv: char, warehouse: &mut Grid<char>, robot_position: Position) -> Position {
+    execute_logged_wide_movement(mv, warehouse, robot_position, &mut Vec::new())
+}
+
+
+fn execute_logged_wide_movement(mv: char, warehouse: &mut Grid<char>, robot_position: Position, log: &mut Vec<Change>) -> Position {
+    let Some(direction) = Move::from_char(&mv) else {
+        return robot_position;
+    };
+    let Some(cells) = pushed_cells(warehouse, robot_position, &direction) else {
+        return robot_position;
+    };
+
+    // all cells move at once, so clear them before they are put down again
+    let values: Vec<char> = cells.iter().map(|cell| warehouse[*cell]).collect();
+    cells.iter().for_each(|cell| write(warehouse, cell, '.', log));
+    for (cell, value) in cells.iter().zip(values) {
+        write(warehouse, &cell.move_to(&direction).unwrap(), value, log);
+    }
+    robot_position.move_to(&direction).unwrap()
+}
+
+
+// everything that moves when the robot pushes, or None when any of it is stopped by a wall;
+// pushing half of a wide box up or down pushes the other half as well
+fn pushed_cells(warehouse: &Grid<char>, robot_position: Position, direction: &Move) -> Option<Vec<Position>> {
+    let vertical = matches!(direction, Move::TOP | Move::DOWN);
+    let mut cells = vec![robot_position];
+    let mut idx = 0;
+    while idx < cells.len() {
+        let next = cells[idx].move_to(direction)?;
+        idx += 1;
+        let pushed = match warehouse.at(&next)? {
+            '#' => return None,
+            'O' => vec![next],
+            '[' if vertical => vec![next, next.move_to(&Move::RIGHT)?],
+            ']' if vertical => vec![next, next.move_to(&Move::LEFT)?],
+            '[' | ']' => vec![next],
+            _ => vec![],
+        };
+        for cell in pushed {
+            if !cells.contains(&cell) {
+                cells.push(cell);
+            }
+        }
+    }
+    Some(cells)
+}
+
+
+impl Solution for Day15 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 15;
+    const INPUT: &'static str = "src/day15/input.txt";
+
+    type Input = (Grid<char>, String);
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        let (grid, movements) = input.split_once("\n\n").ok_or("missing movements")?;
+        let warehouse = Grid::from_str(grid).map_err(|e| e.to_string())?;
+        Ok((warehouse, movements.to_string()))
+    }
+
+    fn part1((warehouse, movements): &Self::Input) -> Result<impl Display, String> {
+        let mut warehouse = warehouse.clone();
+        let robot_position = *warehouse.find_all_positions(|c| *c == '@').first().ok_or("no robot in warehouse")?;
+        execute_movements(movements, &mut warehouse, robot_position);
+        Ok(goods_positioning_all(&warehouse))
+    }
+
+    fn part2((warehouse, movements): &Self::Input) -> Result<impl Display, String> {
+        let mut warehouse = widen(warehouse);
+        let robot_position = *warehouse.find_all_positions(|c| *c == '@').first().ok_or("no robot in warehouse")?;
+        execute_wide_movements(movements, &mut warehouse, robot_position);
+        Ok(goods_positioning_all(&warehouse))
+    }
+}
+
+
 #[cfg(test)]
-pub mod day14_tests {
+pub mod day15_tests {
     use super::*;
-    use std::{fs, str::FromStr};
+    use std::str::FromStr;
+    use aoc_utils::grid::recorder::Recorder;
     
 
 #[test]
@@ -47,63 +226,13 @@ fn example1s() {
 // sum of all boxes' GPS coordinates after the robot finishes moving.
     let (grid, movements) = input.split_once("\n\n").unwrap();
     let mut warehouse = Grid::from_str(grid).unwrap();
-    let mut robot_position = warehouse.find_all_positions(|c| *c == '@')[0];
+    let robot_position = warehouse.find_all_positions(|c| *c == '@')[0];
     execute_movements(movements, &mut warehouse, robot_position);
-    warehouse.print();
     let total = goods_positioning_all(&warehouse);
     assert_eq!(2028, total);
 }
 
 
-fn execute_movements(movements: &str, warehouse: &mut Grid<char>, mut robot_position: Position) {
-    for mv in movements.chars() {
-        if let Some(robot_moving_direction) = Move::from_char(&mv) {
-            if let Some(new_position) = move_position(warehouse, &Track { value: '@', position: robot_position }, &robot_moving_direction) {
-                robot_position = new_position;
-            }
-        }
-    }
-}
-
-
-fn goods_positioning_all(warehouse: &Grid<char>) -> usize {
-    warehouse.find_all_positions(|&c| c == 'O').iter()
-             .map(|good_position| goods_positioning_system(good_position))
-             .sum()
-}
-
-
-fn goods_positioning_system(item: &Position) -> usize {
-    100 * item.y + item.x
-}
-
-
-fn move_position(warehouse: &mut Grid<char>, item: &Track, mv: &Move) -> Option<Position> {
-    if let Some(next_position) = item.position.move_to(mv) {
-        match warehouse.at(&next_position).unwrap() {
-            '.' => {
-                warehouse.set(&item.position, '.');
-                warehouse.set(&next_position, item.value);
-                return Some(next_position);
-            },
-            '#' => {
-                // cannot move
-            },
-            'O' => {
-                // try and move box
-                if let Some(moved_next) = move_position(warehouse, &Track { value: 'O', position: next_position }, mv) {
-                    warehouse.set(&item.position, '.');
-                    warehouse.set(&next_position, item.value);
-                    return Some(next_position);    
-                }
-            }, 
-            _ => {}
-        }
-    }
-    None
-}
-
-
 #[test]
 fn example1() {
     let input = 
@@ -143,26 +272,72 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
     let (grid, movements) = input.split_once("\n\n").unwrap();
     let mut warehouse = Grid::from_str(grid).unwrap();
-    let mut robot_position = warehouse.find_all_positions(|c| *c == '@')[0];
+    let robot_position = warehouse.find_all_positions(|c| *c == '@')[0];
     execute_movements(movements, &mut warehouse, robot_position);
-    warehouse.print();
     let total = goods_positioning_all(&warehouse);
     assert_eq!(10092, total);
 }
 
 
 #[test]
-fn part1() {
-    let input = fs::read_to_string("src/day15/input.txt").unwrap();
+fn example1_replay() {
+    let input = 
+"########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<";
+
     let (grid, movements) = input.split_once("\n\n").unwrap();
-    let mut warehouse = Grid::from_str(grid).unwrap();
+    let mut warehouse: Grid<char> = Grid::from_str(grid).unwrap();
     let mut robot_position = warehouse.find_all_positions(|c| *c == '@')[0];
-    execute_movements(movements, &mut warehouse, robot_position);
-    let total = goods_positioning_all(&warehouse);
-    assert_eq!(1398947, total);
+    let mut recorder = Recorder::new(warehouse.width, warehouse.height).scale(16);
+    recorder.record(&warehouse, palette).unwrap();
+    for mv in movements.chars() {
+        robot_position = execute_movement(mv, &mut warehouse, robot_position);
+        recorder.record(&warehouse, palette).unwrap();
+    }
+
+    assert_eq!(movements.len() + 1, recorder.len());
+    assert_eq!(Position{x: 4, y: 4}, robot_position);
+    assert_eq!(2028, goods_positioning_all(&warehouse));
 }
 
 
+// writes ../target/day15/example1s.gif, run with `cargo test record_example1s -- --ignored`
+#[test]
+#[ignore]
+fn record_example1s() {
+    let input = 
+"########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<";
+
+    let (grid, movements) = input.split_once("\n\n").unwrap();
+    let mut warehouse: Grid<char> = Grid::from_str(grid).unwrap();
+    let mut robot_position = warehouse.find_all_positions(|c| *c == '@')[0];
+    let mut recorder = Recorder::new(warehouse.width, warehouse.height).scale(16);
+    recorder.record(&warehouse, palette).unwrap();
+    for mv in movements.chars() {
+        robot_position = execute_movement(mv, &mut warehouse, robot_position);
+        recorder.record(&warehouse, palette).unwrap();
+    }
+    recorder.save_gif("../target/day15/example1s.gif").unwrap();
+    assert_eq!(movements.len() + 1, recorder.len());
+}
+
 
 #[test]
 fn example2() {
@@ -202,53 +377,168 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 // ####################
 
     let (grid, movements) = input.split_once("\n\n").unwrap();
-    let mut warehouse = Grid::from_str(grid).unwrap();
-    let mut robot_position = warehouse.find_all_positions(|c| *c == '@')[0];
-    execute_movements(movements, &mut warehouse, robot_position);
-    warehouse.print();
+    let mut warehouse = widen(&Grid::from_str(grid).unwrap());
+    let robot_position = warehouse.find_all_positions(|c| *c == '@')[0];
+    execute_wide_movements(movements, &mut warehouse, robot_position);
+    assert_eq!(
+"####################
+##[].......[].[][]##
+##[]...........[].##
+##[]........[][][]##
+##[]......[]....[]##
+##..##......[]....##
+##..[]............##
+##..@......[].[][]##
+##......[][]..[]..##
+####################
+", warehouse.to_string());
     let total = goods_positioning_all(&warehouse);
-    assert_eq!(10092, total);
+    assert_eq!(9021, total);
 }
 
-// #[test]
-// fn part2() {
-//     let input = fs::read_to_string("src/day14/input.txt").unwrap();
-//     let robots = create_robots(&input);
-//     let gridsize = Position{x: 101, y: 103};
-//     let mut stdout = std::io::stdout();
-
-//     let robot_positions: Vec<Position> = robots.iter()
-//                                                 .map(|r| r.move_n_within_grid(IDX_OF_TREE, &gridsize))
-//                                                 .collect();
-
-//     // save positions to file
-//     match fs::exists(IMAGE_FOLDER) {
-//         Ok(false) => fs::create_dir(IMAGE_FOLDER).unwrap(),
-//         _ => {},
-//     }
-//     let mut img = RgbImage::from_pixel(gridsize.x.try_into().unwrap(), gridsize.y.try_into().unwrap(), Rgb([0, 0, 0]));
-//     robot_positions.iter().for_each(|p| img.put_pixel(p.x.try_into().unwrap(), p.y.try_into().unwrap(), Rgb([31, 118, 5])));
-//     img.save(format!("{}/img{}.png", IMAGE_FOLDER, IDX_OF_TREE)).unwrap();
-    
-//     // show positions in console (shift content up 30 lines to show full image)
-//     stdout.queue(terminal::Clear(terminal::ClearType::All)).unwrap()
-//           .queue(cursor::MoveTo(110,0)).unwrap()
-//           .queue(style::PrintStyledContent(format!("MAP {}", IDX_OF_TREE).magenta())).unwrap();
-//     for y in 30..gridsize.y {
-//         for x in 0..gridsize.x {
-//             stdout.queue(cursor::MoveTo(x.try_into().unwrap(), (y - 30).try_into().unwrap())).unwrap();
-//             if robot_positions.contains(&Position{x: x, y: y}) {
-//                 stdout.queue(style::PrintStyledContent("█".green())).unwrap();
-//             } else {
-//                 stdout.queue(style::Print(" ")).unwrap();
-//             }
-//         }
-//     }
-//     stdout
-//         .queue(cursor::MoveToNextLine(1)).unwrap()
-//         .flush().unwrap();
-
-//     assert_eq!(6577, IDX_OF_TREE);
-// }
+
+
+#[test]
+fn example2s() {
+    let input = 
+"#######
+#...#.#
+#.....#
+#..OO@#
+#..O..#
+#.....#
+#######
+
+<vv<<^^<<^^";
+
+    let (grid, movements) = input.split_once("\n\n").unwrap();
+    let mut warehouse = widen(&Grid::from_str(grid).unwrap());
+    assert_eq!("##############\n##......##..##\n##..........##\n##....[][]@.##\n##....[]....##\n##..........##\n##############\n", warehouse.to_string());
+
+    let robot_position = warehouse.find_all_positions(|c| *c == '@')[0];
+    execute_wide_movements(movements, &mut warehouse, robot_position);
+    assert_eq!(
+"##############
+##...[].##..##
+##...@.[]...##
+##....[]....##
+##..........##
+##..........##
+##############
+", warehouse.to_string());
+    assert_eq!(105 + 207 + 306, goods_positioning_all(&warehouse));
+}
+
+
+#[test]
+fn wide_push_is_atomic() {
+    // the right box of the tree hits a wall, so nothing moves
+    let mut warehouse: Grid<char> = Grid::from_str("#######\n#...#.#\n#..[]..\n#.[][].\n#..[]..\n#...@..\n#######").unwrap();
+    let before = warehouse.to_string();
+    let robot_position = Position{x: 4, y: 5};
+    assert_eq!(robot_position, execute_wide_movement('^', &mut warehouse, robot_position));
+    assert_eq!(before, warehouse.to_string());
+
+    // without the wall the whole tree moves up
+    warehouse.set(&Position{x: 4, y: 1}, '.');
+    assert_eq!(Position{x: 4, y: 4}, execute_wide_movement('^', &mut warehouse, robot_position));
+    assert_eq!("#######\n#..[].#\n#.[][].\n#..[]..\n#...@..\n#......\n#######\n", warehouse.to_string());
+}
+
+
+
+#[test]
+fn replay_log() {
+    let input = 
+"########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv
+<v>>v<<";
+
+    let (grid, movements) = input.split_once("\n\n").unwrap();
+    let start = Grid::from_str(grid).unwrap();
+    let mut replay = replay::Replay::record(&start, movements, execute_logged_movement).unwrap();
+    assert_eq!(15, replay.len());
+    assert_eq!(15, replay.cursor());
+    assert_eq!(2028, goods_positioning_all(replay.warehouse()));
+
+    // every prefix gives the same warehouse as running only those movements
+    let movements: String = movements.lines().collect();
+    for n in [0, 1, 4, 9, 15, 3] {
+        let mut expected = start.clone();
+        execute_movements(&movements[..n], &mut expected, Position{x: 2, y: 2});
+        assert_eq!(expected.to_string(), replay.jump(n).unwrap().to_string());
+        assert_eq!(expected.find_all_positions(|c| *c == '@')[0], replay.robot());
+    }
+    assert!(replay.jump(16).is_err());
+
+    let blocked: Vec<usize> = replay.steps().iter().enumerate().filter(|(_, step)| step.blocked()).map(|(nr, _)| nr + 1).collect();
+    assert_eq!(vec![1, 3, 6, 8, 15], blocked);
+    let export = replay.export();
+    assert_eq!(Some("1 < [2,2] blocked"), export.lines().next());
+    // pushing a column of boxes only changes its ends
+    assert_eq!(Some("7 v [4,2] [4,1] @>. [4,2] O>@ [4,6] .>O"), export.lines().nth(6));
+
+    let step = replay.undo().unwrap().clone();
+    assert_eq!(('^', Position{x: 2, y: 1}, true), (step.movement, step.robot_after, step.blocked()));
+    assert_eq!(2, replay.cursor());
+    assert_eq!(step, *replay.redo().unwrap());
+    assert_eq!(3, replay.cursor());
+    replay.jump(0).unwrap();
+    assert!(replay.undo().is_none());
+    assert_eq!(start.to_string(), replay.warehouse().to_string());
+    replay.jump(15).unwrap();
+    assert!(replay.redo().is_none());
+    assert_eq!(2028, goods_positioning_all(replay.warehouse()));
+}
+
+
+#[test]
+fn replay_wide() {
+    let start = widen(&Grid::from_str("#######\n#...#.#\n#.....#\n#..OO@#\n#..O..#\n#.....#\n#######").unwrap());
+    let mut replay = replay::Replay::record(&start, "<vv<<^^<<^^", execute_logged_wide_movement).unwrap();
+    assert_eq!(105 + 207 + 306, goods_positioning_all(replay.warehouse()));
+
+    // the first push moves two boxes left, both halves of each
+    let first = &replay.steps()[0];
+    assert_eq!(6, first.changes.len());
+    assert!(replay.steps()[6].blocked());
+    replay.jump(7).unwrap();
+    assert_eq!("##############\n##......##..##\n##...[][]...##\n##....[]....##\n##.....@....##\n##..........##\n##############\n",
+               replay.warehouse().to_string());
+}
+
+
+// moves like the robot, and every movement also drops or picks up a box in the top left corner
+fn mover_with_side_effect(movement: char, warehouse: &mut Grid<char>, robot: Position, log: &mut Vec<Change>) -> Position {
+    let corner = Position{x: 1, y: 1};
+    write(warehouse, &corner, if warehouse[corner] == 'O' { '.' } else { 'O' }, log);
+    execute_logged_movement(movement, warehouse, robot, log)
+}
+
+
+#[test]
+fn replay_any_mover() {
+    let start = Grid::from_str("######\n#....#\n#.O@.#\n#....#\n######").unwrap();
+    let movements = "<<>v^";
+    let mut replay = replay::Replay::record(&start, movements, mover_with_side_effect).unwrap();
+    assert!(replay.steps().iter().all(|step| step.changes.iter().any(|change| change.position == Position{x: 1, y: 1})));
+    // moving into the wall still drops or picks up the box, but is blocked
+    assert_eq!(vec![false, true, false, false, false], replay.steps().iter().map(|step| step.blocked()).collect::<Vec<_>>());
+    for n in [2, 0, 5, 1, 3] {
+        let mut expected = start.clone();
+        let mut robot = Position{x: 3, y: 2};
+        movements[..n].chars().for_each(|movement| robot = mover_with_side_effect(movement, &mut expected, robot, &mut Vec::new()));
+        assert_eq!(expected.to_string(), replay.jump(n).unwrap().to_string());
+        assert_eq!(robot, replay.robot());
+    }
+}
 
 }
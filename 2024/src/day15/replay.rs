@@ -0,0 +1,170 @@
+use std::fmt::Write;
+
+use aoc_utils::grid::Grid;
+use aoc_utils::position::Position;
+use aoc_utils::Move;
+
+
+/// Tries one movement and returns where the robot ends up, like `execute_movement`;
+/// it must [write] every cell it changes, so that the cell ends up in the log.
+pub type Mover = fn(char, &mut Grid<char>, Position, &mut Vec<Change>) -> Position;
+
+
+/// A cell of the warehouse that a movement changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Change {
+    pub position: Position,
+    pub before: char,
+    pub after: char,
+}
+
+
+/// One movement of the robot; it is blocked when the robot stays where it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub movement: char,
+    pub robot_before: Position,
+    pub robot_after: Position,
+    pub changes: Vec<Change>,
+}
+
+
+impl Step {
+
+    pub fn blocked(&self) -> bool {
+        self.robot_before == self.robot_after
+    }
+
+}
+
+
+/// Sets a cell of the warehouse and adds the change to the log.
+pub fn write(warehouse: &mut Grid<char>, position: &Position, value: char, log: &mut Vec<Change>) {
+    log.push(Change { position: *position, before: warehouse[*position], after: value });
+    warehouse.set(position, value);
+}
+
+
+// a cell written more than once goes from its first value to its last, and is left out when those are the same
+fn merge(log: Vec<Change>) -> Vec<Change> {
+    let mut changes: Vec<Change> = Vec::new();
+    for written in log {
+        match changes.iter_mut().find(|change| change.position == written.position) {
+            Some(change) => change.after = written.after,
+            None => changes.push(written),
+        }
+    }
+    changes.retain(|change| change.before != change.after);
+    changes.sort_by_key(|change| (change.position.y, change.position.x));
+    changes
+}
+
+
+/// The warehouse after any number of the recorded movements, which can be walked back and forth.
+pub struct Replay {
+    warehouse: Grid<char>,
+    robot: Position,
+    log: Vec<Step>,
+    cursor: usize,
+}
+
+
+impl Replay {
+
+    /// Runs all movements with the mover and keeps the diff of each; anything but a movement is left out.
+    /// The replay starts at the end, after the last movement.
+    pub fn record(warehouse: &Grid<char>, movements: &str, mover: Mover) -> Result<Replay, String> {
+        let mut warehouse = warehouse.clone();
+        let mut robot = *warehouse.find_all_positions(|c| *c == '@').first().ok_or("no robot in warehouse")?;
+        let mut log = Vec::new();
+
+        for movement in movements.chars() {
+            if Move::from_char(&movement).is_none() {
+                continue;
+            }
+            let mut written = Vec::new();
+            let robot_after = mover(movement, &mut warehouse, robot, &mut written);
+            log.push(Step { movement, robot_before: robot, robot_after, changes: merge(written) });
+            robot = robot_after;
+        }
+
+        let cursor = log.len();
+        Ok(Replay { warehouse, robot, log, cursor })
+    }
+
+    /// The warehouse after the first `cursor` movements.
+    pub fn warehouse(&self) -> &Grid<char> {
+        &self.warehouse
+    }
+
+    pub fn robot(&self) -> Position {
+        self.robot
+    }
+
+    /// How many movements have been done.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn len(&self) -> usize {
+        self.log.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.log.is_empty()
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.log
+    }
+
+    /// Takes back the last done movement, returns it or None at the start.
+    pub fn undo(&mut self) -> Option<&Step> {
+        self.cursor = self.cursor.checked_sub(1)?;
+        let step = &self.log[self.cursor];
+        step.changes.iter().for_each(|change| self.warehouse.set(&change.position, change.before));
+        self.robot = step.robot_before;
+        Some(step)
+    }
+
+    /// Does the next movement again, returns it or None at the end.
+    pub fn redo(&mut self) -> Option<&Step> {
+        let step = self.log.get(self.cursor)?;
+        step.changes.iter().for_each(|change| self.warehouse.set(&change.position, change.after));
+        self.robot = step.robot_after;
+        self.cursor += 1;
+        Some(step)
+    }
+
+    /// Goes to the warehouse after the first n movements.
+    pub fn jump(&mut self, n: usize) -> Result<&Grid<char>, String> {
+        if n > self.log.len() {
+            return Err(format!("there are only {} movements", self.log.len()));
+        }
+        while self.cursor > n {
+            self.undo();
+        }
+        while self.cursor < n {
+            self.redo();
+        }
+        Ok(&self.warehouse)
+    }
+
+    /// One line per movement: its number, the movement, where the robot ends and either
+    /// `blocked` or every changed cell as `position before>after`.
+    pub fn export(&self) -> String {
+        let mut out = String::new();
+        for (nr, step) in self.log.iter().enumerate() {
+            write!(out, "{} {} {}", nr + 1, step.movement, step.robot_after).unwrap();
+            if step.blocked() {
+                out.push_str(" blocked");
+            }
+            for change in step.changes.iter() {
+                write!(out, " {} {}>{}", change.position, change.before, change.after).unwrap();
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+}
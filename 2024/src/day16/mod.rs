@@ -1,6 +1,13 @@
-use crate::utils::position::Position;
-use std::{borrow::BorrowMut, collections::HashMap, str::FromStr};
-use crate::utils::{grid::Grid, Move};
+use aoc_utils::position::Position;
+use std::{borrow::BorrowMut, collections::{HashMap, HashSet}, fmt::Display, str::FromStr};
+use aoc_utils::grid::render::{Cell, Color, Render};
+use aoc_utils::grid::svg::Svg;
+use aoc_utils::search::{grid_dijkstra_heading, Heading, Paths};
+use aoc_utils::solution::Solution;
+use aoc_utils::{grid::Grid, Move};
+
+
+pub struct Day16;
 
 
 const START_DIRECTION: Move = Move::RIGHT;
@@ -8,13 +15,15 @@ const START_TILE: char = 'S';
 const END_TILE: char = 'E';
 const WALL: char = '#';
 const TURN_COST: i32 = 1000;
+const STEP_COST: i32 = 1;
+const BEST_PATH_TILE: char = 'O';
 
 
 type CrossRoadsMap = HashMap<Position, [Path; 4]>;
 type CostMap = HashMap<(Position, Position), i32>;
 
 
-struct ReindeerOlympic {
+pub struct ReindeerOlympic {
     maze: Grid<char>,
     start: Position,
     end: Position,
@@ -30,12 +39,12 @@ enum Path {
 
 
 impl FromStr for ReindeerOlympic {
-    type Err = &'static str;
+    type Err = String;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let grid = Grid::from_str(input).unwrap();
-        let start = grid.find_all_positions(|&c| c == START_TILE)[0];
-        let end = grid.find_all_positions(|&c| c == END_TILE)[0];
+        let grid = Grid::from_str(input).map_err(|e| e.to_string())?;
+        let start = *grid.find_all_positions(|&c| c == START_TILE).first().ok_or("no start tile")?;
+        let end = *grid.find_all_positions(|&c| c == END_TILE).first().ok_or("no end tile")?;
         Ok(ReindeerOlympic {
             maze: grid,
             start: start,
@@ -84,6 +93,47 @@ impl ReindeerOlympic {
             }
         }
     }
+
+
+    fn best_paths(&self) -> Paths<Heading> {
+        grid_dijkstra_heading(&self.maze, (self.start, START_DIRECTION), self.end, |&c| c != WALL,
+                              STEP_COST as u64, TURN_COST as u64)
+    }
+
+
+    // tiles on any of the routes with the lowest score, whichever direction the reindeer faces there
+    pub fn best_path_tiles(&self) -> HashSet<Position> {
+        self.best_paths().on_optimal_paths()
+                         .iter()
+                         .map(|(position, _)| *position)
+                         .collect()
+    }
+
+
+    pub fn render_best_paths(&self) -> Render<'_, char, impl Fn(&char) -> Cell> {
+        self.maze.render(|&c| match c {
+                     WALL => Cell::new(c, Color::GRAY),
+                     START_TILE | END_TILE => Cell::new(c, Color::YELLOW),
+                     _ => Cell::new(c, Color::WHITE),
+                 })
+                 .overlay(self.best_path_tiles(), Cell::new(BEST_PATH_TILE, Color::GREEN))
+    }
+
+
+    // one of the routes with the lowest score
+    pub fn svg_best_path(&self) -> Svg {
+        let route: Vec<Position> = self.best_paths().path()
+                                       .unwrap_or_default()
+                                       .iter()
+                                       .map(|(position, _)| *position)
+                                       .collect();
+        self.maze.svg(|&c| match c {
+                     WALL => Color::GRAY,
+                     START_TILE | END_TILE => Color::YELLOW,
+                     _ => Color::WHITE,
+                 })
+                 .path(&route, Color::GREEN)
+    }
 }
 
 
@@ -137,6 +187,32 @@ fn find_route(olympic: &ReindeerOlympic, current: &Position, direction: &Move, c
 }
 
 
+impl Solution for Day16 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 16;
+    const INPUT: &'static str = "src/day16/input.txt";
+
+    type Input = ReindeerOlympic;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        Ok(ReindeerOlympic::from_str(input)?)
+    }
+
+    fn part1(olympic: &Self::Input) -> Result<impl Display, String> {
+        let mut crossroads = olympic.create_crossroad_map();
+        olympic.fill_crossroad_distances(&mut crossroads);
+        find_route(olympic, &olympic.start, &START_DIRECTION, 0, &crossroads, &mut CostMap::new())
+            .ok_or(String::from("no route from start to end"))
+    }
+
+    fn part2(olympic: &Self::Input) -> Result<impl Display, String> {
+        match olympic.best_path_tiles().len() {
+            0 => Err(String::from("no route from start to end")),
+            tiles => Ok(tiles),
+        }
+    }
+}
+
 
 #[cfg(test)]
 pub mod day16_tests {
@@ -201,18 +277,6 @@ fn example1_2() {
 }
 
 
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day16/input.txt").unwrap();
-    let olympic = ReindeerOlympic::from_str(&input).unwrap();
-    let mut crossroads = olympic.create_crossroad_map();
-    olympic.fill_crossroad_distances(&mut crossroads);
-    let cost = find_route(&olympic,&olympic.start, &START_DIRECTION, 0, &crossroads, &mut CostMap::new());
-    let total = cost.unwrap_or_default();
-    assert_eq!(115500, total);
-}
-
-
 #[test]
 fn example2_1() {
     let input = 
@@ -231,19 +295,18 @@ fn example2_1() {
 #.###.#.#.#.#.#
 #S..#.....#...#
 ###############";
-    // find start position
-    // position: [TOP, RIGHT, BOTTOM, LEFT]
-    //           NONE, NONE, NONE, SOME(next crossroad, cost)
-
     let olympic = ReindeerOlympic::from_str(input).unwrap();
-    let mut crossroads = olympic.create_crossroad_map();
-    olympic.fill_crossroad_distances(&mut crossroads);
-    let cost = find_route(&olympic,&olympic.start, &START_DIRECTION, 0, &crossroads, &mut CostMap::new());
-    // wall tiles on route
-    let total = 0;
-    assert_eq!(64, total);
-}
+    let render = olympic.render_best_paths();
+
+    let total = olympic.best_path_tiles().len();
+    assert_eq!(45, total);
+    assert_eq!(45, render.to_string().matches(BEST_PATH_TILE).count());
+    assert_eq!(Some(7036), olympic.best_paths().cost());
 
+    let svg = olympic.svg_best_path();
+    // 7 turns for a score of 7036, the first one is made on the start tile before moving
+    assert_eq!(6, svg.to_string().matches("<circle").count());
+}
 
 
 #[test]
@@ -268,54 +331,27 @@ fn example2_2() {
 #################";
 
     let olympic = ReindeerOlympic::from_str(input).unwrap();
-    let mut crossroads = olympic.create_crossroad_map();
-    olympic.fill_crossroad_distances(&mut crossroads);
-    let cost = find_route(&olympic,&olympic.start, &START_DIRECTION, 0, &crossroads, &mut CostMap::new());
-    // wall tiles on route
-    let total = 0;
+
+    let total = olympic.best_path_tiles().len();
     assert_eq!(64, total);
+    assert_eq!(64, olympic.render_best_paths().to_string().matches(BEST_PATH_TILE).count());
 }
 
 
-// #[test]
-// fn part2() {
-//     let input = fs::read_to_string("src/day14/input.txt").unwrap();
-//     let robots = create_robots(&input);
-//     let gridsize = Position{x: 101, y: 103};
-//     let mut stdout = std::io::stdout();
-
-//     let robot_positions: Vec<Position> = robots.iter()
-//                                                 .map(|r| r.move_n_within_grid(IDX_OF_TREE, &gridsize))
-//                                                 .collect();
-
-//     // save positions to file
-//     match fs::exists(IMAGE_FOLDER) {
-//         Ok(false) => fs::create_dir(IMAGE_FOLDER).unwrap(),
-//         _ => {},
-//     }
-//     let mut img = RgbImage::from_pixel(gridsize.x.try_into().unwrap(), gridsize.y.try_into().unwrap(), Rgb([0, 0, 0]));
-//     robot_positions.iter().for_each(|p| img.put_pixel(p.x.try_into().unwrap(), p.y.try_into().unwrap(), Rgb([31, 118, 5])));
-//     img.save(format!("{}/img{}.png", IMAGE_FOLDER, IDX_OF_TREE)).unwrap();
-    
-//     // show positions in console (shift content up 30 lines to show full image)
-//     stdout.queue(terminal::Clear(terminal::ClearType::All)).unwrap()
-//           .queue(cursor::MoveTo(110,0)).unwrap()
-//           .queue(style::PrintStyledContent(format!("MAP {}", IDX_OF_TREE).magenta())).unwrap();
-//     for y in 30..gridsize.y {
-//         for x in 0..gridsize.x {
-//             stdout.queue(cursor::MoveTo(x.try_into().unwrap(), (y - 30).try_into().unwrap())).unwrap();
-//             if robot_positions.contains(&Position{x: x, y: y}) {
-//                 stdout.queue(style::PrintStyledContent("█".green())).unwrap();
-//             } else {
-//                 stdout.queue(style::Print(" ")).unwrap();
-//             }
-//         }
-//     }
-//     stdout
-//         .queue(cursor::MoveToNextLine(1)).unwrap()
-//         .flush().unwrap();
-
-//     assert_eq!(6577, IDX_OF_TREE);
-// }
+// writes ../target/day16/best_path.svg, run with `cargo test record_best_path -- --ignored`
+#[test]
+#[ignore]
+fn record_best_path() {
+    let input = fs::read_to_string("src/day16/input.txt").unwrap();
+    let olympic = ReindeerOlympic::from_str(&input).unwrap();
+    olympic.svg_best_path().save("../target/day16/best_path.svg").unwrap();
+}
+
+
+#[test]
+fn invalid_input() {
+    assert_eq!(Err(String::from("no start tile")), ReindeerOlympic::from_str("#.E#").map(|_| ()));
+    assert!(ReindeerOlympic::from_str("#SE#\n##").is_err());
+}
 
 }
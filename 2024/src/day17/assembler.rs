@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use super::disassembler::{Instruction, Operand};
+use super::{Computer, Program};
+
+
+fn parse_number(text: &str) -> Result<u8, String> {
+    match text.parse() {
+        Ok(value) if value <= 7 => Ok(value),
+        _ => Err(format!("'{}' is not a 3-bit number", text)),
+    }
+}
+
+
+fn parse_combo(text: &str) -> Result<Operand, String> {
+    match text {
+        "A" | "a" => Ok(Operand::A),
+        "B" | "b" => Ok(Operand::B),
+        "C" | "c" => Ok(Operand::C),
+        _ => match Operand::combo(parse_number(text)?) {
+            Operand::RESERVED => Err(String::from("combo operand 7 is reserved")),
+            operand => Ok(operand),
+        },
+    }
+}
+
+
+// The instruction, and the label a jnz jumps to, which is resolved once all labels are known.
+fn parse_statement(text: &str) -> Result<(Instruction, Option<&str>), String> {
+    let mut words = text.split_whitespace();
+    let mnemonic = words.next().unwrap_or_default();
+    let given = words.next();
+    if let Some(extra) = words.next() {
+        return Err(format!("unexpected '{}'", extra));
+    }
+    let operand = || given.ok_or(format!("{} needs an operand", mnemonic));
+
+    let instruction = match mnemonic {
+        "adv" => Instruction::ADV(parse_combo(operand()?)?),
+        "bxl" => Instruction::BXL(parse_number(operand()?)?),
+        "bst" => Instruction::BST(parse_combo(operand()?)?),
+        "jnz" => match operand()? {
+            target if target.starts_with(|c: char| c.is_ascii_digit()) => Instruction::JNZ(parse_number(target)?),
+            label => return Ok((Instruction::JNZ(0), Some(label))),
+        },
+        "bxc" => Instruction::BXC(given.map(parse_number).transpose()?.unwrap_or_default()),
+        "out" => Instruction::OUT(parse_combo(operand()?)?),
+        "bdv" => Instruction::BDV(parse_combo(operand()?)?),
+        "cdv" => Instruction::CDV(parse_combo(operand()?)?),
+        _ => return Err(format!("unknown mnemonic '{}'", mnemonic)),
+    };
+    Ok((instruction, None))
+}
+
+
+/// Assembles mnemonic source into opcodes and operands.
+///
+/// Statements are separated by newlines or `;`, `#` starts a comment, `name:` labels the next
+/// statement and `jnz name` jumps to it. Combo operands are `A`, `B`, `C` or a 3-bit number, so
+/// a listing from the disassembler assembles back into the same program.
+pub fn assemble(source: &str) -> Result<Vec<u8>, String> {
+    let mut labels = HashMap::new();
+    let mut statements = Vec::new();
+
+    for (nr, line) in source.lines().enumerate() {
+        let fail = |e: String| format!("line {}: {}", nr + 1, e);
+        let code = line.split_once('#').map_or(line, |(code, _)| code);
+        for mut text in code.split(';').map(str::trim) {
+            while let Some((label, rest)) = text.split_once(':') {
+                let (label, address) = (label.trim(), statements.len() * 2);
+                if label.starts_with(|c: char| c.is_ascii_digit()) {
+                    if label.parse() != Ok(address) {
+                        return Err(fail(format!("address {} does not match {}", label, address)));
+                    }
+                } else if label.is_empty() || label.contains(char::is_whitespace) {
+                    return Err(fail(format!("invalid label '{}'", label)));
+                } else if labels.insert(label, address).is_some() {
+                    return Err(fail(format!("label '{}' is defined twice", label)));
+                }
+                text = rest.trim();
+            }
+            if !text.is_empty() {
+                statements.push((nr + 1, parse_statement(text).map_err(fail)?));
+            }
+        }
+    }
+
+    let mut commands = Vec::new();
+    for (line, (instruction, target)) in statements {
+        let instruction = match target {
+            None => instruction,
+            Some(label) => match labels.get(label) {
+                Some(&address) if address <= 7 => Instruction::JNZ(address as u8),
+                Some(&address) => return Err(format!("line {}: label '{}' at {} is out of reach of jnz", line, label, address)),
+                None => return Err(format!("line {}: unknown label '{}'", line, label)),
+            },
+        };
+        let (opcode, operand) = instruction.encode();
+        commands.extend([opcode, operand]);
+    }
+    Ok(commands)
+}
+
+
+impl Computer {
+
+    pub fn new(register_a: i64, register_b: i64, register_c: i64, commands: Vec<u8>) -> Computer {
+        Computer {
+            program: Program { register_A: register_a, register_B: register_b, register_C: register_c, output: Vec::new() },
+            commands,
+        }
+    }
+
+}
+
+
+/// The puzzle format, which `Computer::from_str` reads back.
+impl Display for Computer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Register A: {}", self.program.register_A)?;
+        writeln!(f, "Register B: {}", self.program.register_B)?;
+        writeln!(f, "Register C: {}", self.program.register_C)?;
+        writeln!(f)?;
+        write!(f, "Program: {}", self.commands.iter().map(|command| command.to_string()).collect::<Vec<_>>().join(","))
+    }
+}
@@ -0,0 +1,146 @@
+use super::disassembler::{Instruction, Operand};
+use super::{Computer, InstructionResult, Program};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Registers {
+    pub a: i64,
+    pub b: i64,
+    pub c: i64,
+}
+
+
+/// One executed instruction with the registers before and after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub ip: usize,
+    pub instruction: Instruction,
+    pub before: Registers,
+    pub after: Registers,
+    pub output: Option<i32>,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Stops when the instruction pointer reaches the address, before that instruction runs.
+    IP(usize),
+    /// Stops as soon as the program has written this many values.
+    OUTPUTS(usize),
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stop {
+    HALTED,
+    BREAKPOINT(Breakpoint),
+    BUDGET,
+    FAULT(String),
+}
+
+
+pub struct Debugger {
+    program: Program,
+    commands: Vec<u8>,
+    ip: usize,
+    trace: Vec<Step>,
+    breakpoints: Vec<Breakpoint>,
+    budget: Option<usize>,
+}
+
+
+impl Program {
+    fn registers(&self) -> Registers {
+        Registers { a: self.register_A, b: self.register_B, c: self.register_C }
+    }
+}
+
+
+impl Debugger {
+
+    pub fn new(computer: &Computer) -> Debugger {
+        Debugger {
+            program: computer.program.clone(),
+            commands: computer.commands.clone(),
+            ip: 0,
+            trace: Vec::new(),
+            breakpoints: Vec::new(),
+            budget: None,
+        }
+    }
+
+    pub fn break_at(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.push(breakpoint);
+    }
+
+    /// Stops after this many executed instructions in total, so that endless loops end.
+    pub fn set_budget(&mut self, instructions: usize) {
+        self.budget = Some(instructions);
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn registers(&self) -> Registers {
+        self.program.registers()
+    }
+
+    pub fn output(&self) -> &[i32] {
+        &self.program.output
+    }
+
+    pub fn trace(&self) -> &[Step] {
+        &self.trace
+    }
+
+    pub fn step(&mut self) -> Result<&Step, Stop> {
+        if self.ip + 1 >= self.commands.len() {
+            return Err(Stop::HALTED);
+        }
+        if self.budget.is_some_and(|budget| self.trace.len() >= budget) {
+            return Err(Stop::BUDGET);
+        }
+
+        let (opcode, operand) = (self.commands[self.ip], self.commands[self.ip + 1]);
+        let instruction = Instruction::decode(opcode, operand)
+                                      .map_err(|e| Stop::FAULT(format!("address {}: {}", self.ip, e)))?;
+        if instruction.combo() == Some(Operand::RESERVED) {
+            return Err(Stop::FAULT(format!("address {}: reserved combo operand 7", self.ip)));
+        }
+
+        let before = self.program.registers();
+        let result = self.program.do_instruction(opcode, operand)
+                                 .map_err(|e| Stop::FAULT(format!("address {}: {}", self.ip, e)))?;
+        let output = match result {
+            InstructionResult::OUTPUT => self.program.output.last().copied(),
+            _ => None,
+        };
+        let ip = self.ip;
+        self.ip = match result {
+            InstructionResult::JUMP(address) => address,
+            _ => self.ip + 2,
+        };
+
+        self.trace.push(Step { ip, instruction, before, after: self.program.registers(), output });
+        Ok(self.trace.last().unwrap())
+    }
+
+    /// Runs until the program halts, a breakpoint is hit, the budget is used up or an instruction faults.
+    pub fn run(&mut self) -> Stop {
+        loop {
+            let output = match self.step() {
+                Ok(step) => step.output,
+                Err(stop) => return stop,
+            };
+            for breakpoint in self.breakpoints.iter() {
+                match breakpoint {
+                    Breakpoint::IP(address) if *address == self.ip => return Stop::BREAKPOINT(*breakpoint),
+                    Breakpoint::OUTPUTS(count) if output.is_some() && *count == self.program.output.len() => return Stop::BREAKPOINT(*breakpoint),
+                    _ => {},
+                }
+            }
+        }
+    }
+
+}
@@ -0,0 +1,166 @@
+use std::fmt::Display;
+
+use super::{adv, bdv, bst, bxc, bxl, cdv, jnz, out};
+
+
+/// A combo operand resolved to what it reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    LITERAL(u8),
+    A,
+    B,
+    C,
+    RESERVED,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    ADV(Operand),
+    BXL(u8),
+    BST(Operand),
+    JNZ(u8),
+    BXC(u8),
+    OUT(Operand),
+    BDV(Operand),
+    CDV(Operand),
+}
+
+
+impl Operand {
+
+    pub fn combo(value: u8) -> Operand {
+        match value {
+            0..=3 => Operand::LITERAL(value),
+            4 => Operand::A,
+            5 => Operand::B,
+            6 => Operand::C,
+            _ => Operand::RESERVED,
+        }
+    }
+
+    pub fn encode(&self) -> u8 {
+        match self {
+            Operand::LITERAL(value) => *value,
+            Operand::A => 4,
+            Operand::B => 5,
+            Operand::C => 6,
+            Operand::RESERVED => 7,
+        }
+    }
+
+}
+
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operand::LITERAL(value) => write!(f, "{}", value),
+            Operand::A => f.write_str("A"),
+            Operand::B => f.write_str("B"),
+            Operand::C => f.write_str("C"),
+            Operand::RESERVED => f.write_str("<reserved>"),
+        }
+    }
+}
+
+
+impl Instruction {
+
+    #[allow(non_upper_case_globals)]
+    pub fn decode(opcode: u8, operand: u8) -> Result<Instruction, String> {
+        if operand > 7 {
+            return Err(format!("operand {} is not a 3-bit number", operand));
+        }
+        match opcode {
+            adv => Ok(Instruction::ADV(Operand::combo(operand))),
+            bxl => Ok(Instruction::BXL(operand)),
+            bst => Ok(Instruction::BST(Operand::combo(operand))),
+            jnz => Ok(Instruction::JNZ(operand)),
+            bxc => Ok(Instruction::BXC(operand)),
+            out => Ok(Instruction::OUT(Operand::combo(operand))),
+            bdv => Ok(Instruction::BDV(Operand::combo(operand))),
+            cdv => Ok(Instruction::CDV(Operand::combo(operand))),
+            _ => Err(format!("unknown opcode {}", opcode)),
+        }
+    }
+
+    pub fn encode(&self) -> (u8, u8) {
+        match self {
+            Instruction::ADV(operand) => (adv, operand.encode()),
+            Instruction::BXL(literal) => (bxl, *literal),
+            Instruction::BST(operand) => (bst, operand.encode()),
+            Instruction::JNZ(literal) => (jnz, *literal),
+            Instruction::BXC(ignored) => (bxc, *ignored),
+            Instruction::OUT(operand) => (out, operand.encode()),
+            Instruction::BDV(operand) => (bdv, operand.encode()),
+            Instruction::CDV(operand) => (cdv, operand.encode()),
+        }
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Instruction::ADV(_) => "adv",
+            Instruction::BXL(_) => "bxl",
+            Instruction::BST(_) => "bst",
+            Instruction::JNZ(_) => "jnz",
+            Instruction::BXC(_) => "bxc",
+            Instruction::OUT(_) => "out",
+            Instruction::BDV(_) => "bdv",
+            Instruction::CDV(_) => "cdv",
+        }
+    }
+
+    /// The combo operand, if the instruction has one.
+    pub fn combo(&self) -> Option<Operand> {
+        match self {
+            Instruction::ADV(operand) | Instruction::BST(operand) | Instruction::OUT(operand) |
+            Instruction::BDV(operand) | Instruction::CDV(operand) => Some(*operand),
+            _ => None,
+        }
+    }
+
+    /// What the instruction does, with the combo operand resolved, e.g. `A = A >> B`.
+    pub fn meaning(&self) -> String {
+        match self {
+            Instruction::ADV(operand) => format!("A = A >> {}", operand),
+            Instruction::BXL(literal) => format!("B ^= {}", literal),
+            Instruction::BST(operand) => format!("B = {} % 8", operand),
+            Instruction::JNZ(literal) => format!("if A != 0 jump {}", literal),
+            Instruction::BXC(_) => String::from("B ^= C"),
+            Instruction::OUT(operand) => format!("out {} % 8", operand),
+            Instruction::BDV(operand) => format!("B = A >> {}", operand),
+            Instruction::CDV(operand) => format!("C = A >> {}", operand),
+        }
+    }
+
+}
+
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (_, operand) = self.encode();
+        write!(f, "{} {}    # {}", self.mnemonic(), operand, self.meaning())
+    }
+}
+
+
+/// Pairs of opcode and operand with their address, e.g. `  4: bxl 5    # B ^= 5`.
+pub fn disassemble(commands: &[u8]) -> Result<Vec<(usize, Instruction)>, String> {
+    if !commands.len().is_multiple_of(2) {
+        return Err(format!("opcode {} at {} has no operand", commands[commands.len() - 1], commands.len() - 1));
+    }
+    commands.chunks(2)
+            .enumerate()
+            .map(|(nr, pair)| Instruction::decode(pair[0], pair[1])
+                                           .map(|instruction| (nr * 2, instruction))
+                                           .map_err(|e| format!("address {}: {}", nr * 2, e)))
+            .collect()
+}
+
+
+pub fn listing(commands: &[u8]) -> Result<String, String> {
+    Ok(disassemble(commands)?.iter()
+                             .map(|(address, instruction)| format!("{:>3}: {}\n", address, instruction))
+                             .collect())
+}
@@ -1,7 +1,20 @@
+use std::fmt::Display;
 use std::ops::{BitXor, Rem};
 use std::str::FromStr;
+use aoc_utils::solution::Solution;
 use std::{i32, usize};
 
+use debugger::Debugger;
+use disassembler::{Instruction, Operand};
+
+pub mod assembler;
+pub mod debugger;
+pub mod disassembler;
+pub mod quine;
+
+
+pub struct Day17;
+
 
 const adv: u8 = 0;
 const bxl: u8 = 1;
@@ -13,7 +26,8 @@ const bdv: u8 = 6;
 const cdv: u8 = 7;
 
 
-struct Computer {
+#[derive(Clone)]
+pub struct Computer {
     program: Program,
     commands: Vec<u8>    
 }
@@ -44,56 +58,63 @@ impl FromStr for Computer {
     type Err = &'static str;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let (registers, program_str) = input.split_once("\n\n").unwrap();
+        let (registers, program_str) = input.split_once("\n\n").ok_or("missing program")?;
+        let commands: Vec<u8> = program_str.trim()
+                                           .split_once(": ").ok_or("missing program")?.1
+                                           .split(",")
+                                           .map(|d| d.parse().map_err(|_| "invalid command"))
+                                           .collect::<Result<_, _>>()?;
+        if commands.iter().any(|command| *command > 7) {
+            return Err("commands are 3-bit numbers");
+        }
+        if !commands.len().is_multiple_of(2) {
+            return Err("every opcode needs an operand");
+        }
+        if commands.chunks(2).any(|pair| Instruction::decode(pair[0], pair[1]).is_ok_and(|instruction| instruction.combo() == Some(Operand::RESERVED))) {
+            return Err("combo operand 7 is reserved");
+        }
         Ok(Computer {
             program: Program::from_str(registers.trim())?,
-            commands: program_str.trim().split_once(": ").unwrap().1.split(",").map(|d| d.parse().unwrap()).collect(),
+            commands,
         })
     }
 }
 
 
-fn detect_program_to_result(computer: &mut Computer, digit_to_check: i64, nr_outputs_to_match: usize, output_to_cmp: &Vec<i32>) -> Option<i64> {
-    for next_part in 0..8 {
-        let new_value = (digit_to_check * 8) + next_part;
-        let mut program2 = Program {
-            register_A: new_value,
-            register_B: computer.program.register_B,
-            register_C: computer.program.register_C,
-            output: Vec::new(),
-        };
-
-        // do stuff
-        program2.run_commands(&computer.commands);
-
-        if program2.output.len() == nr_outputs_to_match && program2.has_output_match(output_to_cmp) {
-            if program2.output.len() == output_to_cmp.len() {
-                return Some(new_value);
-            } else {
-                // match next digit
-                match detect_program_to_result(computer, new_value, nr_outputs_to_match + 1, output_to_cmp) {
-                    Some(val) => {return Some(val);},
-                    None => {},
-                }
-            }
-        }
+impl Computer {
+
+    fn detect_program(&self) -> Result<i64, String> {
+        quine::analyse(&self.commands)?.minimal(&self.commands)?
+                                       .ok_or(String::from("no value of register A makes the program output itself"))
     }
-    None
-}
 
 
-impl Computer {
+    fn run_program(&mut self) -> Result<Vec<i32>, String> {
+        self.program.run_commands(&self.commands)?;
+        Ok(self.program.output.clone())
+    }
+
 
-    fn detect_program(&mut self) -> i64 {
-        let output_to_cmp: Vec<i32> = self.commands.iter().map(|&d| i32::from(d)).collect();
-        detect_program_to_result(self, 0, 1, &output_to_cmp).unwrap_or_default()
+    pub fn disassemble(&self) -> Result<Vec<(usize, Instruction)>, String> {
+        disassembler::disassemble(&self.commands)
     }
 
 
-    fn run_program(&mut self) -> Vec<i32> {
-        self.program.run_commands(&self.commands);
-        self.program.output.clone()
+    pub fn debugger(&self) -> Debugger {
+        Debugger::new(self)
+    }
+}
+
+
+// num / 2^shift truncated; from a shift of 64 on no i64 reaches the denominator, so the quotient is 0
+fn divide(num: i64, shift: i64) -> Result<i64, String> {
+    if shift < 0 {
+        return Err(format!("a negative shift of {} divides by a fraction", shift));
+    }
+    if shift >= 64 {
+        return Ok(0);
     }
+    Ok((i128::from(num) / (1_i128 << shift)) as i64)
 }
 
 
@@ -101,17 +122,17 @@ impl FromStr for Program {
     type Err = &'static str;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let (reg_a, reg_b, reg_c) = 
-                   input.lines()
-                        .map(|line| line.split_once(": ").unwrap())
-                        .fold((0, 0, 0), |res, (register, value)| {
-                            match register {
-                                "Register A" => (value.parse().unwrap(), res.1, res.2),
-                                "Register B" => (res.0, value.parse().unwrap(), res.2),
-                                "Register C" => (res.0, res.1, value.parse().unwrap()),
-                                _ => res,
-                            }
-                        });
+        let (mut reg_a, mut reg_b, mut reg_c) = (0, 0, 0);
+        for line in input.lines() {
+            let (register, value) = line.split_once(": ").ok_or("missing register value")?;
+            let value = value.parse().map_err(|_| "invalid register value")?;
+            match register {
+                "Register A" => reg_a = value,
+                "Register B" => reg_b = value,
+                "Register C" => reg_c = value,
+                _ => return Err("unknown register"),
+            }
+        }
         Ok(Program {
             register_A: reg_a,
             register_B: reg_b,
@@ -125,47 +146,41 @@ impl FromStr for Program {
 impl Program {
 
 
-    fn has_output_match(&self, expected: &Vec<i32>) -> bool {
-        self.output.iter().rev()
-                    .zip(expected.iter().rev())
-                    .take(self.output.len())
-                    .all(|(a,b)| a == b)
-    }
-
-
-    fn run_commands(&mut self, commands: &Vec<u8>) {
+    // halts when the instruction pointer leaves the program, or when an opcode there has no operand after it
+    fn run_commands(&mut self, commands: &[u8]) -> Result<(), String> {
         let mut instruction_pointer = 0;
-        while instruction_pointer < commands.len() {
+        while instruction_pointer + 1 < commands.len() {
             let opcode = commands[instruction_pointer];
             let operand = commands[instruction_pointer + 1];
     
             // do stuff
-            match self.do_instruction(opcode, operand) {
+            match self.do_instruction(opcode, operand).map_err(|e| format!("address {}: {}", instruction_pointer, e))? {
                 InstructionResult::JUMP(jmp) => instruction_pointer = jmp,
                 _ => instruction_pointer += 2,
             };
         }
+        Ok(())
     }
 
 
-    fn get_operand_value(&self, op_type: OperandType, value: u8) -> i64 {
+    fn get_operand_value(&self, op_type: OperandType, value: u8) -> Result<i64, String> {
         match op_type {
             // he value of a literal operand is the operand itself. For example, the value of the literal operand 7 is the number 7. 
-            OperandType::LITERAL => value.into(),
+            OperandType::LITERAL => Ok(value.into()),
             // The value of a combo operand can be found as follows:
             OperandType::COMBO => match value {
-                0..=3 => value.into(), // represent literal values 0 through 3.
-                4 => self.register_A, // represents the value of register A.
-                5 => self.register_B, // represents the value of register B.
-                6 => self.register_C, // represents the value of register C.
-                7 => todo!("is reserved and will not appear in valid programs."),
-                _ => unimplemented!(),
+                0..=3 => Ok(value.into()), // represent literal values 0 through 3.
+                4 => Ok(self.register_A), // represents the value of register A.
+                5 => Ok(self.register_B), // represents the value of register B.
+                6 => Ok(self.register_C), // represents the value of register C.
+                7 => Err(String::from("combo operand 7 is reserved")), // is reserved and will not appear in valid programs.
+                _ => Err(format!("operand {} is not a 3-bit number", value)),
             },
         }
     }
 
 
-    fn do_instruction(&mut self, instr: u8, operand: u8) -> InstructionResult {
+    fn do_instruction(&mut self, instr: u8, operand: u8) -> Result<InstructionResult, String> {
         match instr {
             adv => {
             // The adv instruction (opcode 0) performs division. 
@@ -173,22 +188,21 @@ impl Program {
                 // The denominator is found by raising 2 to the power of the instruction's combo operand. (So, an operand of 2 would divide A by 4 (2^2); an operand of 5 would divide A by 2^B.) 
                 // The result of the division operation is truncated to an integer and then written to the A register.
                 let num = self.register_A;
-                let dom = 2_i64.pow(self.get_operand_value(OperandType::COMBO, operand).try_into().unwrap());
-                self.register_A = num.wrapping_div(dom);
+                self.register_A = divide(num, self.get_operand_value(OperandType::COMBO, operand)?)?;
             },
             bxl => {
                 // The bxl instruction (opcode 1) calculates the bitwise XOR of register B and the instruction's literal operand, then stores the result in register B.
-                self.register_B = self.register_B.bitxor(self.get_operand_value(OperandType::LITERAL, operand));
+                self.register_B = self.register_B.bitxor(self.get_operand_value(OperandType::LITERAL, operand)?);
             },
             bst => {
                 // The bst instruction (opcode 2) calculates the value of its combo operand modulo 8 (thereby keeping only its lowest 3 bits), then writes that value to the B register.
-                self.register_B = self.get_operand_value(OperandType::COMBO, operand).rem(8);
+                self.register_B = self.get_operand_value(OperandType::COMBO, operand)?.rem(8);
             },
             jnz => {
                 // The jnz instruction (opcode 3) does nothing if the A register is 0. 
                 // However, if the A register is not zero, it jumps by setting the instruction pointer to the value of its literal operand; if this instruction jumps, the instruction pointer is not increased by 2 after this instruction.
                 if self.register_A != 0 {
-                    return InstructionResult::JUMP(self.get_operand_value(OperandType::LITERAL, operand).try_into().unwrap());
+                    return Ok(InstructionResult::JUMP(self.get_operand_value(OperandType::LITERAL, operand)?.try_into().unwrap()));
                 }
             },
             bxc => {
@@ -197,33 +211,52 @@ impl Program {
             },
             out => {
                 // The out instruction (opcode 5) calculates the value of its combo operand modulo 8, then outputs that value. (If a program outputs multiple values, they are separated by commas.)
-                let output_value = self.get_operand_value(OperandType::COMBO, operand).rem(8);
+                let output_value = self.get_operand_value(OperandType::COMBO, operand)?.rem(8);
                 self.output.push(output_value.try_into().unwrap());
-                return InstructionResult::OUTPUT;
+                return Ok(InstructionResult::OUTPUT);
             },
             bdv => {
                 // The bdv instruction (opcode 6) works exactly like the adv instruction except that the result is stored in the B register. (The numerator is still read from the A register.)
                 let num = self.register_A;
-                let dom = 2_i64.pow(self.get_operand_value(OperandType::COMBO, operand).try_into().unwrap());
-                self.register_B = num.wrapping_div(dom);
+                self.register_B = divide(num, self.get_operand_value(OperandType::COMBO, operand)?)?;
             },
             cdv => {
                 // The cdv instruction (opcode 7) works exactly like the adv instruction except that the result is stored in the C register. (The numerator is still read from the A register.)
                 let num = self.register_A;
-                let dom = 2_i64.pow(self.get_operand_value(OperandType::COMBO, operand).try_into().unwrap());
-                self.register_C = num.wrapping_div(dom);
+                self.register_C = divide(num, self.get_operand_value(OperandType::COMBO, operand)?)?;
             },
             _ => {},
         }
-        InstructionResult::REGISTER_UPD
+        Ok(InstructionResult::REGISTER_UPD)
+    }
+
+}
+
+
+impl Solution for Day17 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 17;
+    const INPUT: &'static str = "src/day17/input.txt";
+
+    type Input = Computer;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        Ok(Computer::from_str(input)?)
+    }
+
+    fn part1(computer: &Self::Input) -> Result<impl Display, String> {
+        let output: Vec<String> = computer.clone().run_program()?.iter().map(|d| d.to_string()).collect();
+        Ok(output.join(","))
     }
 
+    fn part2(computer: &Self::Input) -> Result<impl Display, String> {
+        computer.detect_program()
+    }
 }
 
 
 #[cfg(test)]
 pub mod day17_tests {
-    use itertools::Itertools;
     use super::*;
 
 
@@ -237,7 +270,7 @@ Register C: 0
 Program: 0,1,5,4,3,0";
 
     let mut computer = Computer::from_str(input).unwrap();
-    let total = computer.run_program();
+    let total = computer.run_program().unwrap();
     assert_eq!(vec![4,6,3,5,6,3,5,2,1,0], total);
 }
 
@@ -252,7 +285,7 @@ Register C: 0
 Program: 5,0,5,1,5,4";
 
     let mut computer = Computer::from_str(input).unwrap();
-    let total = computer.run_program();
+    let total = computer.run_program().unwrap();
     assert_eq!(vec![0,1,2], total);
 }
 
@@ -267,21 +300,11 @@ Register C: 0
 Program: 0,1,5,4,3,0";
 
     let mut computer = Computer::from_str(input).unwrap();
-    let total = computer.run_program();
+    let total = computer.run_program().unwrap();
     assert_eq!(vec![4,2,5,6,7,7,7,7,3,1,0], total);
 }
 
 
-#[test]
-fn part1() {
-    let input = std::fs::read_to_string("src/day17/input.txt").unwrap();
-    let mut computer = Computer::from_str(&input).unwrap();
-    let total = computer.run_program();
-    assert_eq!(vec![2, 0, 4, 2, 7, 0, 1, 0, 3], total);
-    assert_eq!("2,0,4,2,7,0,1,0,3", total.iter().map(|d| format!("{}", d)).join(","));
-}
-
-
 #[test]
 fn example2() {
     let input = 
@@ -291,20 +314,175 @@ Register C: 0
 
 Program: 0,3,5,4,3,0";
 
-    let mut computer = Computer::from_str(input).unwrap();
+    let computer = Computer::from_str(input).unwrap();
     let total = computer.detect_program();
-    assert_eq!(117440, total);
+    assert_eq!(Ok(117440), total);
 }
 
 
+
 #[test]
-fn part2() {
-    let input = std::fs::read_to_string("src/day17/input.txt").unwrap();
-    let mut computer = Computer::from_str(&input).unwrap();
-    let total = computer.detect_program();
-    assert_ne!(2147482647, total);
-    assert_eq!(265601188299675, total);
+fn disassemble() {
+    let computer = Computer::from_str("Register A: 2024\nRegister B: 0\nRegister C: 0\n\nProgram: 2,4,1,5,7,5,4,3,0,3,5,5,3,0").unwrap();
+    let listing = disassembler::listing(&computer.commands).unwrap();
+    assert_eq!(
+"  0: bst 4    # B = A % 8
+  2: bxl 5    # B ^= 5
+  4: cdv 5    # C = A >> B
+  6: bxc 3    # B ^= C
+  8: adv 3    # A = A >> 3
+ 10: out 5    # out B % 8
+ 12: jnz 0    # if A != 0 jump 0
+", listing);
+
+    let instructions = computer.disassemble().unwrap();
+    let encoded: Vec<u8> = instructions.iter().flat_map(|(_, instruction)| { let (opcode, operand) = instruction.encode(); [opcode, operand] }).collect();
+    assert_eq!(computer.commands, encoded);
+    assert!(disassembler::disassemble(&[0, 1, 5]).is_err());
 }
 
 
+#[test]
+fn debugger_trace() {
+    let mut computer = Computer::from_str("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0").unwrap();
+    let mut debugger = computer.debugger();
+    assert_eq!(debugger::Stop::HALTED, debugger.run());
+    assert_eq!(computer.run_program().unwrap().as_slice(), debugger.output());
+    assert_eq!(30, debugger.trace().len());
+
+    let first = &debugger.trace()[0];
+    assert_eq!((0, Instruction::ADV(disassembler::Operand::LITERAL(1))), (first.ip, first.instruction));
+    assert_eq!((729, 364), (first.before.a, first.after.a));
+    assert_eq!(Some(4), debugger.trace()[1].output);
+}
+
+
+#[test]
+fn debugger_breakpoints() {
+    let computer = Computer::from_str("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0").unwrap();
+    let mut debugger = computer.debugger();
+    debugger.break_at(debugger::Breakpoint::OUTPUTS(3));
+    assert_eq!(debugger::Stop::BREAKPOINT(debugger::Breakpoint::OUTPUTS(3)), debugger.run());
+    assert_eq!(&[4, 6, 3], debugger.output());
+
+    debugger.break_at(debugger::Breakpoint::IP(4));
+    assert_eq!(debugger::Stop::BREAKPOINT(debugger::Breakpoint::IP(4)), debugger.run());
+    assert_eq!(4, debugger.ip());
+    assert_eq!(&[4, 6, 3, 5], debugger.output());
+}
+
+
+#[test]
+fn large_shifts() {
+    // a shift beyond u32 leaves nothing of A in C, then A = i64::MIN / 2^63
+    let computer = Computer::from_str("Register A: -9223372036854775808\nRegister B: 63\nRegister C: 5000000000\n\nProgram: 7,6,0,5").unwrap();
+    let mut debugger = computer.debugger();
+    assert_eq!(debugger::Stop::HALTED, debugger.run());
+    let registers = debugger.registers();
+    assert_eq!((-1, 63, 0), (registers.a, registers.b, registers.c));
+    assert_eq!(Ok(vec![]), computer.clone().run_program());
+
+    let negative = Computer::from_str("Register A: 8\nRegister B: -1\nRegister C: 0\n\nProgram: 0,5").unwrap();
+    assert!(matches!(negative.debugger().run(), debugger::Stop::FAULT(_)));
+    assert_eq!(Err(String::from("address 0: a negative shift of -1 divides by a fraction")), negative.clone().run_program());
+}
+
+
+#[test]
+fn debugger_budget() {
+    let computer = Computer::from_str("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,1,3,0").unwrap();
+    let mut debugger = computer.debugger();
+    debugger.set_budget(100);
+    assert_eq!(debugger::Stop::BUDGET, debugger.run());
+    assert_eq!(100, debugger.trace().len());
+
+    let faulty = Computer::new(1, 0, 0, vec![5, 7]);
+    assert!(matches!(faulty.debugger().run(), debugger::Stop::FAULT(_)));
+}
+
+
+
+#[test]
+fn quine_analysis() {
+    let computer = Computer::from_str("Register A: 2024\nRegister B: 0\nRegister C: 0\n\nProgram: 0,3,5,4,3,0").unwrap();
+    let analysis = quine::analyse(&computer.commands).unwrap();
+    assert_eq!((3, 6), (analysis.shift, analysis.window));
+
+    // B = A % 8 ^ 7, C = A >> B reaches up to 7 + 3 bits
+    let analysis = quine::analyse(&[2,4,1,7,7,5,1,7,0,3,4,1,5,5,3,0]).unwrap();
+    assert_eq!((3, 10), (analysis.shift, analysis.window));
+}
+
+
+#[test]
+fn quine_solutions() {
+    let analysis = quine::analyse(&[0,3,5,4,3,0]).unwrap();
+    assert_eq!(Ok(Some(117440)), analysis.minimal(&[0,3,5,4,3,0]));
+    assert_eq!(Ok(vec![8, 9, 10, 11, 12, 13, 14, 15]), analysis.solutions(&[1, 0]));
+    assert_eq!(Ok(None), analysis.minimal(&[1, 1]));
+
+    let program = [2,4,1,7,7,5,1,7,0,3,4,1,5,5,3,0];
+    let analysis = quine::analyse(&program).unwrap();
+    for a in analysis.solutions(&program).unwrap() {
+        let mut computer = Computer { program: Program { register_A: a, register_B: 0, register_C: 0, output: Vec::new() }, commands: program.to_vec() };
+        assert_eq!(Ok(program.map(i32::from).to_vec()), computer.run_program());
+    }
+}
+
+
+#[test]
+fn quine_diagnostics() {
+    assert_eq!(Err(String::from("program does not end with jnz 0")), quine::analyse(&[5,0,5,1,5,4]).map(|_| ()));
+    assert_eq!(Err(String::from("address 0: reads B from the previous iteration")), quine::analyse(&[1,1,5,5,0,1,3,0]).map(|_| ()));
+    assert_eq!(Err(String::from("address 2: writes more than one value per iteration")), quine::analyse(&[5,4,5,4,0,1,3,0]).map(|_| ()));
+    assert_eq!(Err(String::from("address 0: A must be shifted once per iteration by a literal of 1 to 3")), quine::analyse(&[0,4,5,4,3,0]).map(|_| ()));
+    assert_eq!(Err(String::from("address 0: shift amount is not bounded")), quine::analyse(&[7,4,5,6,0,3,3,0]).map(|_| ()));
+}
+
+
+
+#[test]
+fn assemble() {
+    let source = "
+# the puzzle input
+loop: bst A; bxl 7
+      cdv B
+      bxl 7; adv 3
+      bxc; out B    # B ^= C
+      jnz loop";
+    assert_eq!(Ok(vec![2,4,1,7,7,5,1,7,0,3,4,0,5,5,3,0]), assembler::assemble(source));
+
+    let commands = vec![2,4,1,5,7,5,4,3,0,3,5,5,3,0];
+    assert_eq!(Ok(commands.clone()), assembler::assemble(&disassembler::listing(&commands).unwrap()));
+
+    assert_eq!(Err(String::from("line 1: unknown label 'start'")), assembler::assemble("out A; jnz start"));
+    assert_eq!(Err(String::from("line 2: combo operand 7 is reserved")), assembler::assemble("adv 1\nout 7"));
+    assert_eq!(Err(String::from("line 1: unknown mnemonic 'mul'")), assembler::assemble("mul A"));
+    assert_eq!(Err(String::from("line 1: label 'end' at 8 is out of reach of jnz")), assembler::assemble("jnz end\nout A\nout A\nout A\nend: out A"));
+}
+
+
+#[test]
+fn write_puzzle_format() {
+    let computer = Computer::new(2024, 0, 0, assembler::assemble("start: adv 3; out A; jnz start").unwrap());
+    let text = computer.to_string();
+    assert_eq!("Register A: 2024\nRegister B: 0\nRegister C: 0\n\nProgram: 0,3,5,4,3,0", text);
+
+    let mut parsed = Computer::from_str(&text).unwrap();
+    assert_eq!(Ok(vec![5,7,3,0]), parsed.run_program());
+}
+
+
+#[test]
+fn invalid_input() {
+    assert_eq!(Err("missing program"), Computer::from_str("garbage line here").map(|_| ()));
+    assert_eq!(Err("invalid command"), Computer::from_str("Register A: 1\n\nProgram: 0,x").map(|_| ()));
+    assert_eq!(Err("commands are 3-bit numbers"), Computer::from_str("Register A: 1\n\nProgram: 0,8").map(|_| ()));
+    assert_eq!(Err("invalid register value"), Computer::from_str("Register A: one\n\nProgram: 0,1").map(|_| ()));
+    assert_eq!(Err("every opcode needs an operand"), Computer::from_str("Register A: 1\n\nProgram: 0,3,5").map(|_| ()));
+    assert_eq!(Err("combo operand 7 is reserved"), Computer::from_str("Register A: 1\n\nProgram: 5,7").map(|_| ()));
+    // a literal 7 is fine
+    assert!(Computer::from_str("Register A: 1\n\nProgram: 1,7").is_ok());
+}
+
 }
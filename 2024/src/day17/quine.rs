@@ -0,0 +1,171 @@
+use super::disassembler::{disassemble, Instruction, Operand};
+use super::Program;
+
+
+/// The shape of a program that loops over register A: every iteration writes one value that only
+/// depends on A, shifts A right by `shift` bits and jumps back to the start while A is not 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    /// Bits A is shifted right per iteration.
+    pub shift: u32,
+    /// Low bits of A at the start of an iteration that decide its output.
+    pub window: usize,
+    body: Vec<u8>,
+}
+
+
+// What a register holds in terms of A: the low bits of A that decide its lowest 3 bits,
+// and if the value is bounded, the low bits of A that decide all of it and its maximum.
+#[derive(Debug, Clone, Copy)]
+struct Value {
+    low: usize,
+    bounded: Option<(usize, i64)>,
+}
+
+
+impl Value {
+
+    fn literal(value: u8) -> Value {
+        Value { low: 0, bounded: Some((0, value.into())) }
+    }
+
+    fn a(shifted: u32) -> Value {
+        Value { low: shifted as usize + 3, bounded: None }
+    }
+
+    fn rem8(&self) -> Value {
+        Value { low: self.low, bounded: Some((self.low, 7)) }
+    }
+
+    fn xor(&self, other: &Value) -> Value {
+        let bounded = match (self.bounded, other.bounded) {
+            (Some((bits, max)), Some((other_bits, other_max))) =>
+                Some((bits.max(other_bits), ((max.max(other_max) + 1) as u64).next_power_of_two() as i64 - 1)),
+            _ => None,
+        };
+        Value { low: self.low.max(other.low), bounded }
+    }
+
+    // A >> self, where A has already been shifted right by `shifted` bits in this iteration
+    fn shift_of_a(&self, shifted: u32) -> Result<Value, String> {
+        let (bits, max) = self.bounded.ok_or("shift amount is not bounded")?;
+        if shifted as i64 + max > 60 {
+            return Err(format!("shift amount may reach {}", max));
+        }
+        Ok(Value { low: bits.max(shifted as usize + max as usize + 3), bounded: None })
+    }
+
+}
+
+
+/// Checks the program has the looping shape the solver relies on and works out how A flows into the outputs.
+pub fn analyse(commands: &[u8]) -> Result<Analysis, String> {
+    let instructions = disassemble(commands)?;
+    let Some((&(_, last), body)) = instructions.split_last() else {
+        return Err(String::from("program is empty"));
+    };
+    if last != Instruction::JNZ(0) {
+        return Err(String::from("program does not end with jnz 0"));
+    }
+
+    let mut shift = None;
+    let mut window = None;
+    let mut b: Option<Value> = None;
+    let mut c: Option<Value> = None;
+    for &(address, instruction) in body {
+        let fail = |reason: &str| format!("address {}: {}", address, reason);
+        let shifted = shift.unwrap_or(0);
+        let combo = move |operand| match operand {
+            Operand::LITERAL(value) => Ok(Value::literal(value)),
+            Operand::A => Ok(Value::a(shifted)),
+            Operand::B => b.ok_or(fail("reads B from the previous iteration")),
+            Operand::C => c.ok_or(fail("reads C from the previous iteration")),
+            Operand::RESERVED => Err(fail("reserved combo operand 7")),
+        };
+
+        match instruction {
+            Instruction::ADV(Operand::LITERAL(bits)) if bits > 0 && shift.is_none() => shift = Some(bits.into()),
+            Instruction::ADV(_) => return Err(fail("A must be shifted once per iteration by a literal of 1 to 3")),
+            Instruction::BXL(literal) => b = Some(b.ok_or(fail("reads B from the previous iteration"))?.xor(&Value::literal(literal))),
+            Instruction::BST(operand) => b = Some(combo(operand)?.rem8()),
+            Instruction::JNZ(_) => return Err(fail("jumps inside the loop")),
+            Instruction::BXC(_) => {
+                let (Some(b_value), Some(c_value)) = (b, c) else {
+                    return Err(fail("reads B or C from the previous iteration"));
+                };
+                b = Some(b_value.xor(&c_value));
+            },
+            Instruction::OUT(operand) if window.is_none() => window = Some(combo(operand)?.low),
+            Instruction::OUT(_) => return Err(fail("writes more than one value per iteration")),
+            Instruction::BDV(operand) => b = Some(combo(operand)?.shift_of_a(shifted).map_err(|e| fail(&e))?),
+            Instruction::CDV(operand) => c = Some(combo(operand)?.shift_of_a(shifted).map_err(|e| fail(&e))?),
+        }
+    }
+
+    Ok(Analysis {
+        shift: shift.ok_or("A is never shifted, so the loop never ends")?,
+        window: window.ok_or("the loop writes no output")?,
+        body: commands[..commands.len() - 2].to_vec(),
+    })
+}
+
+
+impl Analysis {
+
+    /// The value one iteration writes when A holds `a` at its start.
+    pub fn output_of(&self, a: i64) -> u8 {
+        let mut program = Program { register_A: a, register_B: 0, register_C: 0, output: Vec::new() };
+        program.run_commands(&self.body).expect("the analysis bounds every shift of the loop body");
+        program.output[0] as u8
+    }
+
+    /// The smallest A for which the program writes exactly `target`.
+    pub fn minimal(&self, target: &[u8]) -> Result<Option<i64>, String> {
+        self.check(target)?;
+        let mut solutions = Vec::new();
+        self.search(target, target.len(), 0, &mut solutions, false);
+        Ok(solutions.first().copied())
+    }
+
+    /// Every A for which the program writes exactly `target`, in ascending order.
+    pub fn solutions(&self, target: &[u8]) -> Result<Vec<i64>, String> {
+        self.check(target)?;
+        let mut solutions = Vec::new();
+        self.search(target, target.len(), 0, &mut solutions, true);
+        Ok(solutions)
+    }
+
+    fn check(&self, target: &[u8]) -> Result<(), String> {
+        if target.is_empty() {
+            return Err(String::from("every program writes at least one value"));
+        }
+        if let Some(value) = target.iter().find(|&&value| value > 7) {
+            return Err(format!("{} is not a 3-bit output", value));
+        }
+        if self.shift as usize * target.len() > 62 {
+            return Err(format!("{} outputs need more than 62 bits of A", target.len()));
+        }
+        Ok(())
+    }
+
+    // Output i only depends on A >> (shift * i), so A is built from the last output to the first,
+    // `shift` bits at a time; trying the smallest bits first finds the solutions in ascending order.
+    fn search(&self, target: &[u8], remaining: usize, high: i64, solutions: &mut Vec<i64>, all: bool) {
+        if remaining == 0 {
+            solutions.push(high);
+            return;
+        }
+        // the loop only stops after the last output when the highest bits are not all 0
+        let first = if remaining == target.len() && target.len() > 1 { 1 } else { 0 };
+        for bits in first..1 << self.shift {
+            let a = (high << self.shift) | bits;
+            if self.output_of(a) == target[remaining - 1] {
+                self.search(target, remaining - 1, a, solutions, all);
+                if !all && !solutions.is_empty() {
+                    return;
+                }
+            }
+        }
+    }
+
+}
@@ -1,7 +1,11 @@
-use std::fs;
+use std::fmt::Display;
+use aoc_utils::solution::Solution;
 
 
-struct Report {
+pub struct Day2;
+
+
+pub struct Report {
     level: Vec<i32>,
 }
 
@@ -104,7 +108,39 @@ impl Report {
     }
 
 
+}
+
 
+fn create_reports(input: &str) -> Result<Vec<Report>, String> {
+    input.lines()
+         .map(|report| {
+            let level = report.split_whitespace()
+                              .map(|lvl| lvl.parse().map_err(|_| format!("invalid level: {}", lvl)))
+                              .collect::<Result<Vec<i32>, String>>()?;
+            Ok(Report { level })
+         })
+         .collect()
+}
+
+
+impl Solution for Day2 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 2;
+    const INPUT: &'static str = "src/day2/input.txt";
+
+    type Input = Vec<Report>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        create_reports(input)
+    }
+
+    fn part1(reports: &Self::Input) -> Result<impl Display, String> {
+        Ok(reports.iter().filter(|report| report.is_safe()).count())
+    }
+
+    fn part2(reports: &Self::Input) -> Result<impl Display, String> {
+        Ok(reports.iter().filter(|report| report.is_safe_with_dampener()).count())
+    }
 }
 
 
@@ -139,26 +175,6 @@ fn example1() {
 }
 
 
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day2/input.txt").unwrap();
-    let (safe_levels, unsafe_levels): (Vec<Report>, Vec<Report>) =
-            input.lines()
-                .map(|report| report.split_whitespace())
-                .map(|levels| 
-                    Report{
-                    level: levels.into_iter()
-                                 .map(|lvl| lvl.parse().unwrap())
-                                 .collect()
-                })
-                .partition(|level| level.is_safe());
-
-    println!("total safe: {}", safe_levels.len());
-    assert_eq!(510, safe_levels.len());
-    assert_eq!(490, unsafe_levels.len());
-}
-
-
 #[test]
 fn example2() {
     let input =
@@ -184,25 +200,4 @@ fn example2() {
     assert_eq!(2, unsafe_levels.len());
 }
 
-
-
-#[test]
-fn part2() {
-    let input = fs::read_to_string("src/day2/input.txt").unwrap();
-    let (safe_levels, unsafe_levels): (Vec<Report>, Vec<Report>) =
-            input.lines()
-                .map(|report| report.split_whitespace())
-                .map(|levels| 
-                    Report{
-                    level: levels.into_iter()
-                                .map(|lvl| lvl.parse().unwrap())
-                                .collect()
-                })
-                .partition(|level| level.is_safe_with_dampener());
-
-    println!("total safe: {}", safe_levels.len());
-    assert_eq!(553, safe_levels.len());
-    assert_eq!(447, unsafe_levels.len());
 }
-
-}
\ No newline at end of file
@@ -1,5 +1,9 @@
-use std::fs;
+use std::fmt::Display;
 use regex::Regex;
+use aoc_utils::solution::Solution;
+
+
+pub struct Day3;
 
 
 fn calc(input: &str) -> i32 {
@@ -34,6 +38,26 @@ fn calc_with_preinstruction(input: &str) -> i32 {
 }
 
 
+impl Solution for Day3 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 3;
+    const INPUT: &'static str = "src/day3/input.txt";
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(memory: &Self::Input) -> Result<impl Display, String> {
+        Ok(calc(memory))
+    }
+
+    fn part2(memory: &Self::Input) -> Result<impl Display, String> {
+        Ok(calc_with_preinstruction(memory))
+    }
+}
+
 
 #[cfg(test)]
 pub mod day3_tests {
@@ -47,13 +71,6 @@ fn example1() {
 }
 
 
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day3/input.txt").unwrap();
-    assert_eq!(164730528, calc(&input));
-}
-
-
 #[test]
 fn example2() {
     let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
@@ -61,11 +78,4 @@ fn example2() {
     assert_eq!(48, calc_with_preinstruction(input));
 }
 
-
-#[test]
-fn part2() {
-    let input = fs::read_to_string("src/day3/input.txt").unwrap();
-    assert_eq!(70478672, calc_with_preinstruction(&input));
 }
-
-}
\ No newline at end of file
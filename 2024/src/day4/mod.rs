@@ -1,5 +1,9 @@
-use std::{collections::{HashSet}, fs, path::Display};
+use std::{collections::{HashSet}, fmt::Display};
 use itertools::Itertools;
+use aoc_utils::solution::Solution;
+
+
+pub struct Day4;
 
 
 #[derive(Debug, Eq, PartialEq, Hash)]
@@ -203,6 +207,36 @@ fn count_matches_mas(grid: &Grid) -> usize {
 }
 
 
+fn create_grid(input: &str) -> Grid<'_> {
+    let width = input.lines().next().unwrap_or_default().len() + 1;
+    Grid {
+        grid: input,
+        width,
+        height: input.len() / width,
+    }
+}
+
+
+impl Solution for Day4 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 4;
+    const INPUT: &'static str = "src/day4/input.txt";
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(puzzle: &Self::Input) -> Result<impl Display, String> {
+        Ok(count_matches_xmas(&create_grid(puzzle)))
+    }
+
+    fn part2(puzzle: &Self::Input) -> Result<impl Display, String> {
+        Ok(count_matches_mas(&create_grid(puzzle)))
+    }
+}
+
 
 #[cfg(test)]
 pub mod day4_tests {
@@ -283,20 +317,6 @@ MXMXAXMASX";
 }
 
 
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day4/input.txt").unwrap();
-    let width = input.lines().next().unwrap().len() + 1;
-    let grid = Grid {
-        grid: input.as_str(),
-        width: width,
-        height: input.len() / width,
-    };
-
-    assert_eq!(2401, count_matches_xmas(&grid));
-}
-
-
 #[test]
 fn example2() {
     let input = 
@@ -320,17 +340,4 @@ MXMXAXMASX";
     assert_eq!(9, count_matches_mas(&grid));
 }
 
-
-#[test]
-fn part2() {
-    let input = fs::read_to_string("src/day4/input.txt").unwrap();
-    let width = input.lines().next().unwrap().len() + 1;
-    let grid = Grid {
-        grid: input.as_str(),
-        width: width,
-        height: input.len() / width,
-    };
-    assert_eq!(1822, count_matches_mas(&grid));
 }
-
-}
\ No newline at end of file
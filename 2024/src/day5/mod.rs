@@ -1,31 +1,31 @@
-use std::{collections::{HashMap}, fs, str::Lines};
-
-
-fn create_ordering_map(input: Lines) -> HashMap<i32, Vec<i32>> {
-    let page_ordering_rules = input.take_while(|line| !line.is_empty())
-                .map(|line| line.split_once("|").unwrap())
-                .fold(HashMap::new(), |mut order: HashMap<i32, Vec<i32>>, item|{
-                    let page_first: i32 = item.0.parse().unwrap();
-                    let page_after: i32 = item.1.parse().unwrap();
-                    if order.contains_key(&page_first) {
-                        // update
-                        if let Some(list) = order.get_mut(&page_first) {
-                            list.push(page_after);
-                        }
-                    } else {
-                        order.insert(page_first, Vec::from([page_after]));
-                    }
-                    order
-                });
-    page_ordering_rules
+use std::{collections::{HashMap}, fmt::Display, str::Lines};
+use aoc_utils::solution::Solution;
+
+
+pub struct Day5;
+
+
+type Rules = HashMap<i32, Vec<i32>>;
+
+
+fn create_ordering_map(input: Lines) -> Result<Rules, String> {
+    let mut page_ordering_rules: Rules = HashMap::new();
+    for line in input.take_while(|line| !line.is_empty()) {
+        let (first, after) = line.split_once("|").ok_or(format!("invalid rule: {}", line))?;
+        let page_first: i32 = first.parse().map_err(|_| format!("invalid page: {}", first))?;
+        let page_after: i32 = after.parse().map_err(|_| format!("invalid page: {}", after))?;
+        page_ordering_rules.entry(page_first).or_default().push(page_after);
+    }
+    Ok(page_ordering_rules)
 }
 
 
-fn create_page_order(input: Lines) -> Vec<Vec<i32>> {
-    let pages_per_update = input.skip_while(|line| !line.is_empty()).skip(1)
-                        .map(|line| line.split(',').map(|i| i.parse().unwrap()).collect())
-                        .collect();
-    pages_per_update
+fn create_page_order(input: Lines) -> Result<Vec<Vec<i32>>, String> {
+    input.skip_while(|line| !line.is_empty()).skip(1)
+         .map(|line| line.split(',')
+                         .map(|i| i.parse().map_err(|_| format!("invalid page: {}", i)))
+                         .collect::<Result<Vec<i32>, String>>())
+         .collect()
 }
 
 
@@ -45,32 +45,28 @@ fn is_correct_ordering(pages: &Vec<i32>, page_ordering_rules: &HashMap<i32, Vec<
 }
 
 
-fn count_correct_ordering(input: Lines, page_ordering_rules: HashMap<i32, Vec<i32>>) -> i32 {
+fn count_correct_ordering(updates: &[Vec<i32>], page_ordering_rules: &Rules) -> i32 {
     let mut total = 0;
 
-    for pages in create_page_order(input) {
-        let has_right_order = is_correct_ordering(&pages, &page_ordering_rules);
+    for pages in updates {
+        let has_right_order = is_correct_ordering(pages, page_ordering_rules);
         if has_right_order {
             let middle_value : i32 = pages[pages.len() / 2];
-            println!("RIGHT: {:?} => {}", &pages, middle_value);
             total += middle_value;
-        } else {
-            println!("WRONG: {:?}", &pages);
         }
     }
     total
 }
 
 
-fn count_incorrect_ordering(input: Lines, page_ordering_rules: HashMap<i32, Vec<i32>>) -> i32 {
+fn count_incorrect_ordering(updates: &[Vec<i32>], page_ordering_rules: &Rules) -> i32 {
     let empty_vec: Vec<i32> = Vec::new();
     let mut total = 0;
 
-    for mut pages in create_page_order(input) {
-        let has_right_order = is_correct_ordering(&pages, &page_ordering_rules);
+    for pages in updates {
+        let mut pages = pages.clone();
+        let has_right_order = is_correct_ordering(&pages, page_ordering_rules);
         if !has_right_order {
-            print!("REORDER: {:?} ", &pages);
-
             loop {
                 let mut reordered = false;
 
@@ -92,7 +88,6 @@ fn count_incorrect_ordering(input: Lines, page_ordering_rules: HashMap<i32, Vec<
             }
 
             let middle_value : i32 = pages[pages.len() / 2];
-            println!(" --> {:?} => {}", &pages, middle_value);
             total += middle_value;
         }
     }
@@ -100,6 +95,31 @@ fn count_incorrect_ordering(input: Lines, page_ordering_rules: HashMap<i32, Vec<
 }
 
 
+impl Solution for Day5 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 5;
+    const INPUT: &'static str = "src/day5/input.txt";
+
+    type Input = (Rules, Vec<Vec<i32>>);
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        let updates = create_page_order(input.lines())?;
+        if updates.iter().any(|pages| pages.is_empty()) {
+            return Err(String::from("update without pages"));
+        }
+        Ok((create_ordering_map(input.lines())?, updates))
+    }
+
+    fn part1((page_ordering_rules, updates): &Self::Input) -> Result<impl Display, String> {
+        Ok(count_correct_ordering(updates, page_ordering_rules))
+    }
+
+    fn part2((page_ordering_rules, updates): &Self::Input) -> Result<impl Display, String> {
+        Ok(count_incorrect_ordering(updates, page_ordering_rules))
+    }
+}
+
+
 #[cfg(test)]
 pub mod day5_tests {
     use super::*;
@@ -136,21 +156,12 @@ fn example1() {
 61,13,29
 97,13,75,29,47";
 
-    let page_ordering_rules = create_ordering_map(input.lines());
-    let total = count_correct_ordering(input.lines(), page_ordering_rules);
+    let page_ordering_rules = create_ordering_map(input.lines()).unwrap();
+    let total = count_correct_ordering(&create_page_order(input.lines()).unwrap(), &page_ordering_rules);
     assert_eq!(61 + 53 + 29, total); // 75,47,61,53,29  |  97,61,53,29,13  |  75,29,13
 }
 
 
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day5/input.txt").unwrap();
-    let page_ordering_rules = create_ordering_map(input.lines());
-    let total = count_correct_ordering(input.lines(), page_ordering_rules);
-    assert_eq!(5651, total);
-}
-
-
 #[test]
 fn example2() {
     let input = 
@@ -183,18 +194,17 @@ fn example2() {
 61,13,29
 97,13,75,29,47";
 
-    let page_ordering_rules = create_ordering_map(input.lines());
-    let total = count_incorrect_ordering(input.lines(), page_ordering_rules);
+    let page_ordering_rules = create_ordering_map(input.lines()).unwrap();
+    let total = count_incorrect_ordering(&create_page_order(input.lines()).unwrap(), &page_ordering_rules);
     assert_eq!(47 + 29 + 47, total); // 75,97,47,61,53 => 97,75,47,61,53 | 61,13,29 => 61,29,13 | 97,13,75,29,47 => 97,75,47,29,13
 }
 
 
 #[test]
-fn part2() {
-    let input = fs::read_to_string("src/day5/input.txt").unwrap();
-    let page_ordering_rules = create_ordering_map(input.lines());
-    let total = count_incorrect_ordering(input.lines(), page_ordering_rules);
-    assert_eq!(4743, total);
+fn invalid_input() {
+    assert!(Day5::parse("garbage line here").is_err());
+    assert!(Day5::parse("47|53\n\n75,x,61").is_err());
+    assert!(Day5::parse("47|53\n\n75,47\n\n").is_err());
 }
 
-}
\ No newline at end of file
+}
@@ -1,90 +1,241 @@
-use std::{num::ParseIntError, str::FromStr, fs};
+use std::{fmt::Display, str::FromStr};
+use aoc_utils::solution::Solution;
+
+
+pub struct Day7;
 
 
 #[derive(Debug)]
-struct Equation {
+pub struct Equation {
     test_value: i64,
     operators: Vec<i64>,
 }
 
 
-enum Operation {
+/// A binary operator of an equation; equations are evaluated left to right, whatever the operators.
+pub trait Operator {
+    fn symbol(&self) -> &str;
+
+    /// `a op b`, or None when it is not defined for these operands or overflows.
+    fn apply(&self, a: i64, b: i64) -> Option<i64>;
+
+    /// The `a` with `a op b = result`, so that the solver can work back from the test value.
+    /// Operators that cannot tell leave it UNKNOWN, then the solver tries the values left of them.
+    fn inverse(&self, _result: i64, _b: i64) -> Inverse {
+        Inverse::UNKNOWN
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inverse {
+    /// No `a` gives the result.
+    NONE,
+    /// Only this `a` gives the result.
+    ONE(i64),
+    UNKNOWN,
+}
+
+
+pub enum Operation {
     ADD,
     MULTIPLY,
     CONCATENATE
 }
 
 
-impl Equation {
-    fn within_boundary(&self) -> bool {
-        let min_total: i64 = self.operators.iter().sum(); // sum = equal or greater than test_value
-        let max_total: i64 = self.operators.iter().product(); // product = equal or smaller then test_value
-        !(self.test_value < min_total || self.test_value > max_total)
+/// A test value written out with the operators that make it, like `81 * 40 + 27`.
+pub struct Expression<'o> {
+    numbers: Vec<i64>,
+    operators: Vec<&'o dyn Operator>,
+}
+
+
+// the power of ten that shifts a number left by all digits of b
+fn shift_for(b: i64) -> Option<i64> {
+    let mut shift: i64 = 10;
+    while shift <= b {
+        shift = shift.checked_mul(10)?;
     }
+    Some(shift)
 }
 
 
-impl Operation {
-    fn apply(&self, a: &i64, b: &i64) -> i64 {
+impl Operator for Operation {
+    fn symbol(&self) -> &str {
+        match self {
+            Operation::ADD => "+",
+            Operation::MULTIPLY => "*",
+            Operation::CONCATENATE => "||",
+        }
+    }
+
+    fn apply(&self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Operation::ADD => a.checked_add(b),
+            Operation::MULTIPLY => a.checked_mul(b),
+            Operation::CONCATENATE if b < 0 => None,
+            Operation::CONCATENATE => a.checked_mul(shift_for(b)?)?.checked_add(b),
+        }
+    }
+
+    fn inverse(&self, result: i64, b: i64) -> Inverse {
         match self {
-            Operation::ADD => a + b,
-            Operation::MULTIPLY => a * b,
-            Operation::CONCATENATE => {
-                let b_digits = (*b as f64).log10().floor() as u32 + 1;
-                (a * 10i64.pow(b_digits)) + b
+            Operation::ADD => result.checked_sub(b).map_or(Inverse::NONE, Inverse::ONE),
+            // any a times 0 is 0
+            Operation::MULTIPLY if b == 0 => if result == 0 { Inverse::UNKNOWN } else { Inverse::NONE },
+            Operation::MULTIPLY if result % b == 0 => Inverse::ONE(result / b),
+            Operation::MULTIPLY => Inverse::NONE,
+            // the result has to end with the digits of b
+            Operation::CONCATENATE => match (b >= 0, shift_for(b), result.checked_sub(b)) {
+                (true, Some(shift), Some(rest)) if rest % shift == 0 => Inverse::ONE(rest / shift),
+                _ => Inverse::NONE,
             },
         }
     }
 }
 
 
+impl Expression<'_> {
+    pub fn value(&self) -> Option<i64> {
+        self.operators.iter()
+                      .zip(self.numbers.iter().skip(1))
+                      .try_fold(self.numbers[0], |value, (operator, b)| operator.apply(value, *b))
+    }
+}
+
+
+impl Display for Expression<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.numbers[0])?;
+        for (operator, b) in self.operators.iter().zip(self.numbers.iter().skip(1)) {
+            write!(f, " {} {}", operator.symbol(), b)?;
+        }
+        Ok(())
+    }
+}
+
+
+// the operators between the numbers that give the target, working back from the last number
+fn solve_backward<'o>(numbers: &[i64], target: i64, operators: &[&'o dyn Operator]) -> Option<Vec<&'o dyn Operator>> {
+    let (&b, rest) = numbers.split_last()?;
+    if rest.is_empty() {
+        return (b == target).then(Vec::new);
+    }
+    for &operator in operators {
+        let found = match operator.inverse(target, b) {
+            Inverse::NONE => None,
+            Inverse::ONE(a) => solve_backward(rest, a, operators),
+            Inverse::UNKNOWN => solve_forward(rest, operators, &|a| operator.apply(a, b) == Some(target)),
+        };
+        if let Some(mut chain) = found {
+            chain.push(operator);
+            return Some(chain);
+        }
+    }
+    None
+}
+
+
+// the first operators between the numbers, trying them in order, whose value passes the check
+fn solve_forward<'o>(numbers: &[i64], operators: &[&'o dyn Operator], check: &dyn Fn(i64) -> bool) -> Option<Vec<&'o dyn Operator>> {
+    fn expand<'o>(value: i64, rest: &[i64], operators: &[&'o dyn Operator], check: &dyn Fn(i64) -> bool, chain: &mut Vec<&'o dyn Operator>) -> bool {
+        let Some((&b, rest)) = rest.split_first() else {
+            return check(value);
+        };
+        for &operator in operators {
+            if let Some(next) = operator.apply(value, b) {
+                chain.push(operator);
+                if expand(next, rest, operators, check, chain) {
+                    return true;
+                }
+                chain.pop();
+            }
+        }
+        false
+    }
+
+    let (&first, rest) = numbers.split_first()?;
+    let mut chain = Vec::new();
+    expand(first, rest, operators, check, &mut chain).then_some(chain)
+}
+
+
+impl Equation {
+    /// The first way, if any, to put the operators between the numbers so that they give the test value.
+    pub fn solve<'o>(&self, operators: &[&'o dyn Operator]) -> Option<Expression<'o>> {
+        solve_backward(&self.operators, self.test_value, operators)
+            .map(|chain| Expression { numbers: self.operators.clone(), operators: chain })
+    }
+}
+
+
 impl FromStr for Equation {
-    type Err = ParseIntError;
+    type Err = &'static str;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let pair = s.split_once(": ").unwrap();
+        let pair = s.split_once(": ").ok_or("missing test value")?;
+        let operators: Vec<i64> = pair.1.split_whitespace()
+                                        .map(|d| d.parse().map_err(|_| "invalid operator"))
+                                        .collect::<Result<_, _>>()?;
+        if operators.is_empty() {
+            return Err("missing operators");
+        }
         Ok(Equation {
-            test_value: pair.0.parse().unwrap(),
-            operators: pair.1.split_whitespace().map(|d| d.parse().unwrap()).collect()
+            test_value: pair.0.parse().map_err(|_| "invalid test value")?,
+            operators,
         })
     }
 }
 
 
-fn create_equations(input: &str) -> impl Iterator<Item = Equation> + use<'_> {
-    input.lines()
-         .map(|line| Equation::from_str(line).unwrap())
+fn is_match(eq: &Equation, operations: &[&dyn Operator]) -> bool {
+    eq.solve(operations).is_some()
 }
 
 
-fn calc(cur: &i64, prev_totals: Vec<i64>, operations: &[Operation]) -> Vec<i64> {
-    operations.iter()
-              .flat_map(|o| prev_totals.iter()
-                                                   .map(|prev_sum| o.apply(prev_sum, cur)))
-              .collect()
+fn sum_matching(equations: &[Equation], operations: &[&dyn Operator]) -> i64 {
+    equations.iter()
+             .filter(|eq| is_match(eq, operations))
+             .map(|eq| eq.test_value)
+             .sum()
 }
 
 
-fn is_match(eq: &Equation, operations: &[Operation]) -> bool {
-    let mut loop_operators = eq.operators.iter();
-    let mut sum = vec![*loop_operators.next().unwrap()]; // first one requires no calculation
-    for x in loop_operators {
-        let mut new_sum = calc(x, sum, operations);
-        new_sum.sort();
-        new_sum.dedup();
-        new_sum.retain(|&x| x <= eq.test_value);
-        sum = new_sum;
+impl Solution for Day7 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 7;
+    const INPUT: &'static str = "src/day7/input.txt";
+
+    type Input = Vec<Equation>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        input.lines()
+             .map(|line| Equation::from_str(line).map_err(|e| format!("{}: {}", e, line)))
+             .collect()
     }
-    sum.contains(&eq.test_value)
-}
 
+    fn part1(equations: &Self::Input) -> Result<impl Display, String> {
+        Ok(sum_matching(equations, &[&Operation::ADD, &Operation::MULTIPLY]))
+    }
 
+    fn part2(equations: &Self::Input) -> Result<impl Display, String> {
+        Ok(sum_matching(equations, &[&Operation::ADD, &Operation::MULTIPLY, &Operation::CONCATENATE]))
+    }
+}
 
 
 #[cfg(test)]
 pub mod day7_tests {
     use super::*;
 
+
+fn create_equations(input: &str) -> impl Iterator<Item = Equation> + use<'_> {
+    input.lines()
+         .map(|line| Equation::from_str(line).unwrap())
+}
+
+
 #[test]
 fn example1() {
     let input = 
@@ -98,26 +249,13 @@ fn example1() {
 21037: 9 7 18 13
 292: 11 6 16 20";
 
-    let total: i64 = create_equations(&input).filter(|eq| is_match(eq, &[Operation::ADD, Operation::MULTIPLY]))
+    let total: i64 = create_equations(&input).filter(|eq| is_match(eq, &[&Operation::ADD, &Operation::MULTIPLY]))
                                              .map(|eq| eq.test_value)
                                              .sum();
     assert_eq!(3749, total);
 }
 
 
-
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day7/input.txt").unwrap();
-    let total: i64 = 
-    create_equations(&input).filter(|eq| is_match(eq, &[Operation::ADD, Operation::MULTIPLY]))
-                            .map(|eq| eq.test_value)
-                            .sum();
-    assert_ne!(303766878186, total); // too low
-    assert_eq!(303766880536, total);
-}
-
-
 #[test]
 fn example2() {
     let input = 
@@ -132,21 +270,51 @@ fn example2() {
 292: 11 6 16 20";
 
     let total: i64 = 
-    create_equations(&input).filter(|eq| is_match(eq, &[Operation::ADD, Operation::MULTIPLY, Operation::CONCATENATE]))
+    create_equations(&input).filter(|eq| is_match(eq, &[&Operation::ADD, &Operation::MULTIPLY, &Operation::CONCATENATE]))
                             .map(|eq| eq.test_value)
                             .sum();
     assert_eq!(11387, total);
 }
 
 
+
 #[test]
-fn part2() {
-    let input = fs::read_to_string("src/day7/input.txt").unwrap();
-    let total: i64 = 
-    create_equations(&input).filter(|eq| is_match(eq, &[Operation::ADD, Operation::MULTIPLY, Operation::CONCATENATE]))
-                            .map(|eq| eq.test_value)
-                            .sum();
-    assert_eq!(337041851384440, total);
+fn expressions() {
+    let all: [&dyn Operator; 3] = [&Operation::ADD, &Operation::MULTIPLY, &Operation::CONCATENATE];
+    let solve = |line: &str| Equation::from_str(line).unwrap().solve(&all).map(|e| e.to_string());
+    assert_eq!(Some("81 * 40 + 27".to_string()), solve("3267: 81 40 27"));
+    assert_eq!(Some("15 || 6".to_string()), solve("156: 15 6"));
+    assert_eq!(Some("6 * 8 || 6 * 15".to_string()), solve("7290: 6 8 6 15"));
+    assert_eq!(None, solve("83: 17 5"));
+    assert_eq!(Some("0 * 7 + 3".to_string()), solve("3: 0 7 3"));
+
+    let expression = Equation::from_str("192: 17 8 14").unwrap().solve(&all).unwrap();
+    assert_eq!(Some(192), expression.value());
+    assert!(Equation::from_str("192: 17 8 14").unwrap().solve(&all[..2]).is_none());
+}
+
+
+// an operator without an inverse
+struct Subtract;
+
+impl Operator for Subtract {
+    fn symbol(&self) -> &str {
+        "-"
+    }
+
+    fn apply(&self, a: i64, b: i64) -> Option<i64> {
+        a.checked_sub(b)
+    }
+}
+
+
+#[test]
+fn custom_operator() {
+    let operators: [&dyn Operator; 2] = [&Operation::ADD, &Subtract];
+    let expression = Equation::from_str("5: 10 3 2").unwrap().solve(&operators).unwrap();
+    assert_eq!("10 - 3 - 2", expression.to_string());
+    assert_eq!("9 - 3 + 2", Equation::from_str("8: 9 3 2").unwrap().solve(&operators).unwrap().to_string());
+    assert!(Equation::from_str("100: 9 3 2").unwrap().solve(&operators).is_none());
 }
 
 }
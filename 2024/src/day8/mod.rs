@@ -1,9 +1,14 @@
-use std::{collections::HashMap, fs, ops::{Add, Mul}};
+use std::{collections::HashMap, fmt::Display, str::FromStr};
 use itertools::Itertools;
-use crate::utils::{grid::Grid, position::Position};
+use aoc_utils::point::{Point, Vector};
+use aoc_utils::solution::Solution;
+use aoc_utils::{grid::Grid, position::Position};
 
 
-fn get_antenna_map(grid: &Grid<char>) -> HashMap<char, Vec<Position>> {
+pub struct Day8;
+
+
+fn get_antenna_map(grid: &Grid<char>) -> HashMap<char, Vec<Point>> {
     let mut antenna_map = HashMap::new();
 
     for (idx, point) in grid.grid.iter().enumerate() {
@@ -11,7 +16,7 @@ fn get_antenna_map(grid: &Grid<char>) -> HashMap<char, Vec<Position>> {
             '.' => {},
             '\n' => {},
             antenna => {
-                let antenna_position = Position::from(idx, grid);
+                let antenna_position = Point::from_position(&Position::from(idx, grid), grid).unwrap();
                 antenna_map.entry(*antenna).or_insert(Vec::new())
                            .push(antenna_position);
             }
@@ -21,20 +26,17 @@ fn get_antenna_map(grid: &Grid<char>) -> HashMap<char, Vec<Position>> {
 }
 
 
-
-fn find_antinodes(antenna_map: &HashMap<char, Vec<Position>>, grid: &Grid<char>) -> Vec<Position> {
+fn find_antinodes(antenna_map: &HashMap<char, Vec<Point>>, grid: &Grid<char>) -> Vec<Position> {
     let mut antinode = Vec::new();
     
     for (_antenna, locations) in antenna_map {
         for permutation in locations.iter().permutations(2) {
             match permutation[..] {
                 [first, second, ..] => {
-                    let (diff_x, diff_y) = first.distance_to(second);
-                    if diff_x != 0 && diff_y != 0 {
-                        if let Ok(top_x) = (second.x as i32 + diff_x).try_into() {
-                            if let Ok(top_y) = (second.y as i32 + diff_y).try_into() {
-                                antinode.push(Position{x: top_x, y: top_y});
-                            }
+                    let diff = *second - *first;
+                    if diff.x != 0 && diff.y != 0 {
+                        if let Some(position) = (*second + diff).to_position(grid) {
+                            antinode.push(position);
                         }
                     }
                 },
@@ -45,20 +47,18 @@ fn find_antinodes(antenna_map: &HashMap<char, Vec<Position>>, grid: &Grid<char>)
     
     antinode.sort();
     antinode.dedup();
-    antinode.retain(|p| grid.in_grid(p));
     antinode
 }
 
 
-fn find_antinodes_with_resonate(antenna_map: &HashMap<char, Vec<Position>>, grid: &Grid<char>) -> Vec<Position> {
+fn find_antinodes_with_resonate(antenna_map: &HashMap<char, Vec<Point>>, grid: &Grid<char>) -> Vec<Position> {
     let mut antinode = Vec::new();
     
     for (_antenna, locations) in antenna_map {
         for permutation in locations.iter().permutations(2) {
             match permutation[..] {
                 [first, second, ..] => {
-                    let (diff_x, diff_y) = first.distance_to(second);
-                    add_antinodes(grid, diff_x, diff_y, second, &mut antinode, grid.width.max(grid.height));
+                    add_antinodes(grid, *second - *first, second, &mut antinode);
                 },
                 _ => {},
             }
@@ -67,26 +67,40 @@ fn find_antinodes_with_resonate(antenna_map: &HashMap<char, Vec<Position>>, grid
     
     antinode.sort();
     antinode.dedup();
-    antinode.retain(|p| grid.in_grid(p));
     antinode
 }
 
 
-fn add_antinodes(grid: &Grid<char>, diff_x: i32, diff_y: i32, second: &Position, antinode: &mut Vec<Position>, counter: usize) {
-    for i in 0..counter {
-        if let Ok(top_x) = diff_x.mul(i as i32).add(second.x as i32).try_into() {
-            if let Ok(top_y) = diff_y.mul(i as i32).add(second.y as i32).try_into() {
-                let next_position = Position{x: top_x, y: top_y};
-                if !grid.in_grid(&next_position) {
-                    break;
-                }
-                antinode.push(next_position);
-            }
-        }
+fn add_antinodes(grid: &Grid<char>, diff: Vector, second: &Point, antinode: &mut Vec<Position>) {
+    let mut next = *second;
+    while let Some(next_position) = next.to_position(grid) {
+        antinode.push(next_position);
+        next += diff;
     }
 }
 
 
+impl Solution for Day8 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 8;
+    const INPUT: &'static str = "src/day8/input.txt";
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        Grid::from_str(input).map_err(|e| e.to_string())
+    }
+
+    fn part1(grid: &Self::Input) -> Result<impl Display, String> {
+        let antenna_map = get_antenna_map(grid);
+        Ok(find_antinodes(&antenna_map, grid).len())
+    }
+
+    fn part2(grid: &Self::Input) -> Result<impl Display, String> {
+        let antenna_map = get_antenna_map(grid);
+        Ok(find_antinodes_with_resonate(&antenna_map, grid).len())
+    }
+}
 
 
 #[cfg(test)]
@@ -118,20 +132,6 @@ fn example1() {
 }
 
 
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day8/input.txt").unwrap();
-    let grid = Grid::from_str(&input).unwrap();
-    let antenna_map = get_antenna_map(&grid);
-    let antinode = find_antinodes(&antenna_map, &grid).len();
-    let total = antinode;
-    assert_ne!(380, total); // too high
-    assert_ne!(371, total); // too high
-    assert_eq!(367, total); 
-}
-
-
-
 #[test]
 fn example2_sample() {
     let input = 
@@ -154,7 +154,6 @@ fn example2_sample() {
 }
 
 
-
 #[test]
 fn example2() {
     let input = 
@@ -178,14 +177,4 @@ fn example2() {
     assert_eq!(34, total);
 }
 
-
-#[test]
-fn part2() {
-    let input = fs::read_to_string("src/day8/input.txt").unwrap();
-    let grid = Grid::from_str(&input).unwrap();
-    let antenna_map = get_antenna_map(&grid);
-    let antinode = find_antinodes_with_resonate(&antenna_map, &grid).len();
-    let total = antinode;
-    assert_eq!(1285, total); 
-}
 }
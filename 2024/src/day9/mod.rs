@@ -1,4 +1,8 @@
-use std::{fs, str::FromStr, ops::{Add, Mul, Div}};
+use std::{fmt::Display, ops::{Add, Mul, Div}};
+use aoc_utils::solution::Solution;
+
+
+pub struct Day9;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Storage {
@@ -6,7 +10,7 @@ enum Storage {
 }
 
 #[derive(Debug, Clone, Copy, Default)]
-struct FileBlock {
+pub struct FileBlock {
     index: usize,
     size: u32,
     content: Storage,
@@ -28,10 +32,6 @@ impl FileBlock {
         }
     }
 
-    fn print(&self, symbol: char) {
-        (0..self.size).for_each(|_| print!("{symbol}"));
-    }
-
     fn split_at_most(&self, size: u32) -> (u32, u32) {
         let remainder = self.size.checked_sub(size).unwrap_or_default();
         (self.size - remainder, remainder)
@@ -44,11 +44,15 @@ fn do_calc(size: u32, start_idx: u32, index: u32) -> u64 {
 }
 
 
-fn create_filesystem(input: &str) -> Vec<FileBlock> {
+fn create_filesystem(input: &str) -> Result<Vec<FileBlock>, String> {
+    let input = input.trim_end();
+    if input.is_empty() {
+        return Err(String::from("empty disk map"));
+    }
     let mut filesystem = Vec::new();
     for (file_representation_idx, block_size) in input.char_indices() {
-        if !block_size.is_digit(10) {
-            break;
+        if !block_size.is_ascii_digit() {
+            return Err(format!("invalid block size '{}' at {}", block_size, file_representation_idx + 1));
         }
         if file_representation_idx & 1 == 0 {
             // even is file
@@ -68,7 +72,7 @@ fn create_filesystem(input: &str) -> Vec<FileBlock> {
             filesystem.push(f);
         }
     }
-    filesystem
+    Ok(filesystem)
 }
 
 
@@ -114,6 +118,12 @@ fn calculate_checksum(filesystem: &mut Vec<FileBlock>) -> u64 {
 
 
 fn defrag(filesystem: &mut Vec<FileBlock>) {
+    defrag_with(filesystem, |_| ());
+}
+
+
+/// Like `defrag`, and shows `on_move` the disk after every file that moved.
+fn defrag_with<F: FnMut(&[FileBlock])>(filesystem: &mut Vec<FileBlock>, mut on_move: F) {
     for i in (0..filesystem.len()).rev() {
         let current_file = filesystem[i];
         if current_file.content == Storage::FILE {
@@ -124,6 +134,7 @@ fn defrag(filesystem: &mut Vec<FileBlock>) {
                     filesystem[mv].size = used;
                     filesystem.swap(i, mv);
                     filesystem.insert(mv +1, FileBlock { index: free_space.index, size: free_size, content: Storage::FREE });
+                    on_move(filesystem);
                     break;
                 }
             }
@@ -153,10 +164,62 @@ fn calculate_checksum2(filesystem: &mut Vec<FileBlock>) -> u64 {
 }
 
 
+impl Solution for Day9 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 9;
+    const INPUT: &'static str = "src/day9/input.txt";
+
+    type Input = Vec<FileBlock>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        create_filesystem(input)
+    }
+
+    fn part1(filesystem: &Self::Input) -> Result<impl Display, String> {
+        Ok(calculate_checksum(&mut filesystem.clone()))
+    }
+
+    fn part2(filesystem: &Self::Input) -> Result<impl Display, String> {
+        Ok(calculate_checksum2(&mut filesystem.clone()))
+    }
+}
+
 
 #[cfg(test)]
 pub mod day9_tests {
     use super::*;
+    use aoc_utils::grid::{Grid, recorder::Recorder, render::Color};
+    use aoc_utils::position::Position;
+    use std::time::Duration;
+
+
+// the file id of every block, free blocks have none
+fn disk_blocks(filesystem: &[FileBlock]) -> Vec<Option<usize>> {
+    filesystem.iter()
+              .flat_map(|block| (0..block.size).map(move |_| match block.content {
+                  Storage::FILE => Some(block.index),
+                  Storage::FREE => None,
+              }))
+              .collect()
+}
+
+
+fn disk_map(filesystem: &[FileBlock]) -> String {
+    disk_blocks(filesystem).iter()
+                           .map(|block| block.map_or('.', |id| char::from_digit(id as u32 % 10, 10).unwrap()))
+                           .collect()
+}
+
+
+// the blocks wrapped in rows of `width`
+fn disk_grid(filesystem: &[FileBlock], width: usize) -> Grid<Option<usize>> {
+    let blocks = disk_blocks(filesystem);
+    let mut grid = Grid::new(width, blocks.len().div_ceil(width), None);
+    blocks.into_iter()
+          .enumerate()
+          .for_each(|(nr, block)| grid.set(&Position{x: nr % width, y: nr / width}, block));
+    grid
+}
 
 
 #[test]
@@ -169,23 +232,12 @@ fn example1() {
 
     // reformat last to first empty space
     // calculate checksum: 0*0 + 1*0 + 2*9 + 3*9 ...
-    let mut filesystem = create_filesystem(input);
+    let mut filesystem = create_filesystem(input).unwrap();
     let checksum = calculate_checksum(&mut filesystem);
     assert_eq!(1928, checksum);
 }
 
 
-
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day9/input.txt").unwrap();
-    let mut filesystem = create_filesystem(&input);
-    let checksum = calculate_checksum(&mut filesystem);
-    assert_eq!(6421128769094, checksum);
-}
-
-
-
 #[test]
 fn example2() {
     let input = "2333133121414131402";
@@ -195,18 +247,45 @@ fn example2() {
 
     // reformat last to first empty space
     // calculate checksum
-    let mut filesystem = create_filesystem(input);
+    let mut filesystem = create_filesystem(input).unwrap();
     let checksum = calculate_checksum2(&mut filesystem);
     assert_eq!(2858, checksum);
 }
 
 
 #[test]
-fn part2() {
-    let input = fs::read_to_string("src/day9/input.txt").unwrap();
-    let mut filesystem = create_filesystem(&input);
-    let checksum = calculate_checksum2(&mut filesystem);
-    assert_eq!(6448168620520, checksum);
+fn defrag_moves() {
+    let mut filesystem = create_filesystem("2333133121414131402").unwrap();
+    let mut moves = Vec::new();
+    defrag_with(&mut filesystem, |disk| moves.push(disk_map(disk)));
+    assert_eq!(vec![
+        "0099.111...2...333.44.5555.6666.777.8888..",
+        "0099.1117772...333.44.5555.6666.....8888..",
+        "0099.111777244.333....5555.6666.....8888..",
+        "00992111777.44.333....5555.6666.....8888..",
+    ], moves);
+}
+
+
+// writes ../target/day9/defrag.gif, run with `cargo test record_defrag -- --ignored`
+#[test]
+#[ignore]
+fn record_defrag() {
+    let mut filesystem = create_filesystem("2333133121414131402").unwrap();
+    let mut recorder = Recorder::new(14, 3).scale(16).delay(Duration::from_millis(500));
+    let palette = |block: &Option<usize>| block.map_or(Color::BLACK, Color::indexed);
+    recorder.record(&disk_grid(&filesystem, 14), palette).unwrap();
+    defrag_with(&mut filesystem, |disk| recorder.record(&disk_grid(disk, 14), palette).unwrap());
+    recorder.save_gif("../target/day9/defrag.gif").unwrap();
+    assert_eq!(5, recorder.len());
+}
+
+
+#[test]
+fn invalid_input() {
+    assert_eq!(Err(String::from("invalid block size 'x' at 3")), create_filesystem("23x3").map(|_| ()));
+    assert!(Day9::parse("").is_err());
+    assert_eq!(3, Day9::parse("233\n").unwrap().len());
 }
 
 }
@@ -1,11 +1,12 @@
-pub mod utils;
+use std::process::ExitCode;
+
+pub mod runner;
 
 pub mod day1;
 pub mod day2;
 pub mod day3;
 pub mod day4;
 pub mod day5;
-pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
@@ -18,4 +19,7 @@ pub mod day15;
 pub mod day16;
 pub mod day17;
 
-fn main() {}
+
+fn main() -> ExitCode {
+    aoc_utils::cli::main("oac2024", runner::YEAR, runner::registry())
+}
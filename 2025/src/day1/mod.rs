@@ -1,4 +1,8 @@
-use std::{char, fs, ops::RangeInclusive};
+use std::{char, fmt::Display, ops::RangeInclusive};
+use aoc_utils::solution::Solution;
+
+
+pub struct Day1;
 
 
 const _DIAL: RangeInclusive<i32> = 0..=99;
@@ -54,6 +58,59 @@ fn rotate_pass_zero(current_position: i32, rotation: &str) -> (i32, i32) {
 }
 
 
+fn count_zero_positions(rotations: &[String]) -> i32 {
+    let mut position = DIAL_START_POSITION;
+    let mut zero_count = 0;
+    for rotation in rotations {
+        position = rotate(position, rotation);
+        if position == 0 {
+            zero_count += 1;
+        }
+    }
+    zero_count
+}
+
+
+fn count_zero_passes(rotations: &[String]) -> i32 {
+    let mut position = DIAL_START_POSITION;
+    let mut zero_count = 0;
+    for rotation in rotations {
+        let rotate_adjustment = rotate_pass_zero(position, rotation);
+        position = rotate_adjustment.0;
+        if position == 0 {
+            zero_count += 1;
+        }
+        zero_count += rotate_adjustment.1;
+    }
+    zero_count
+}
+
+
+impl Solution for Day1 {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 1;
+    const INPUT: &'static str = "src/day1/input1.txt";
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        input.lines()
+             .map(|line| match line.split_at_checked(1) {
+                Some(("L" | "R", amount)) if amount.parse::<i32>().is_ok() => Ok(line.to_string()),
+                _ => Err(format!("invalid rotation: {}", line)),
+             })
+             .collect()
+    }
+
+    fn part1(rotations: &Self::Input) -> Result<impl Display, String> {
+        Ok(count_zero_positions(rotations))
+    }
+
+    fn part2(rotations: &Self::Input) -> Result<impl Display, String> {
+        Ok(count_zero_passes(rotations))
+    }
+}
+
 
 #[cfg(test)]
 pub mod day1_tests {
@@ -109,23 +166,6 @@ L82";
     assert_eq!(3, result);
 }
 
-#[test]
-fn part1() {
-    // the number of times the dial is left pointing at 0 after any rotation in the sequence
-    let input = fs::read_to_string("src/day1/input1.txt").unwrap();
-    let mut position = DIAL_START_POSITION;
-    let mut zero_count = 0;
-    input.lines().for_each(|line| {
-        position = rotate(position, line);
-        if position == 0 {
-            zero_count += 1;
-        }
-        println!("The dial is rotated {} to point at {}", line, position);
-    });
-    let result = zero_count;
-    assert_eq!(1023, result);
-}
-
 
 // password method 0x434C49434B
 #[test]
@@ -182,29 +222,4 @@ L82";
     assert_eq!(6, result);
 }
 
-#[test]
-fn part2() {
-    // the number of times the dial is left pointing at 0 after any rotation in the sequence
-    let input = fs::read_to_string("src/day1/input1.txt").unwrap();
-    let mut position = DIAL_START_POSITION;
-    let mut zero_count = 0;
-    input.lines().for_each(|line| {
-        let rotate_adjustment = rotate_pass_zero(position, line);
-        position = rotate_adjustment.0;
-        if position == 0 {
-            zero_count += 1;
-            zero_count += rotate_adjustment.1;
-        } else {
-            zero_count += rotate_adjustment.1;
-        }
-        println!("The dial is rotated {} to point at {} (at zero {})", line, position, rotate_adjustment.1);
-    });
-    let result = zero_count;
-    assert_ne!(1023, result);
-    assert_ne!(5900, result); // high
-    assert_ne!(4876, result); // low
-    assert_eq!(5899, result);
 }
-
-
-}
\ No newline at end of file
@@ -1,4 +1,8 @@
-use std::{char, ops::RangeInclusive};
+use std::{char, fmt::Display, ops::RangeInclusive};
+use aoc_utils::solution::Solution;
+
+
+pub struct Day2;
 
 
 fn calculate_invalid_sum(input: &str) -> u64 {
@@ -51,10 +55,37 @@ fn calculate_invalid_sum2(input: &str) -> u64 {
 }
 
 
+impl Solution for Day2 {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 2;
+    const INPUT: &'static str = "src/day2/input1.txt";
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        let ranges = input.trim();
+        for pair in ranges.split(',') {
+            match pair.split_once('-') {
+                Some((start, end)) if start.parse::<i64>().is_ok() && end.parse::<i64>().is_ok() => {},
+                _ => return Err(format!("invalid range: {}", pair)),
+            }
+        }
+        Ok(ranges.to_string())
+    }
+
+    fn part1(ranges: &Self::Input) -> Result<impl Display, String> {
+        Ok(calculate_invalid_sum(ranges))
+    }
+
+    fn part2(ranges: &Self::Input) -> Result<impl Display, String> {
+        Ok(calculate_invalid_sum2(ranges))
+    }
+}
+
+
 #[cfg(test)]
 pub mod day2_tests {
     use super::*;
-    use std::fs;
 
 #[test]
 fn example1_1() {
@@ -99,23 +130,4 @@ Adding up all the invalid IDs in this example produces 4174379265.
 */
 }
 
-
-#[test]
-fn part1() {
-    // the number of times the dial is left pointing at 0 after any rotation in the sequence
-    let input = fs::read_to_string("src/day2/input1.txt").unwrap();
-    let total = calculate_invalid_sum(input.as_str());
-    assert_eq!(15873079081, total);
-}
-
-
-#[test]
-fn part2() {
-    // the number of times the dial is left pointing at 0 after any rotation in the sequence
-    let input = fs::read_to_string("src/day2/input1.txt").unwrap();
-    let total = calculate_invalid_sum2(input.as_str());
-    assert_ne!(15873079081, total);
-    assert_eq!(22617871034, total);
 }
-
-}
\ No newline at end of file
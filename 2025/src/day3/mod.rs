@@ -1,3 +1,8 @@
+use std::fmt::Display;
+use aoc_utils::solution::Solution;
+
+
+pub struct Day3;
 
 
 fn find_largest_joltage(bank: &str) -> u64 {
@@ -42,11 +47,35 @@ fn find_largest_joltage12(bank: &str) -> u64 {
 }
 
 
+impl Solution for Day3 {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 3;
+    const INPUT: &'static str = "src/day3/input1.txt";
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        input.lines()
+             .map(|bank| match bank.chars().all(|c| c.is_ascii_digit()) && bank.len() > 12 {
+                true => Ok(bank.to_string()),
+                false => Err(format!("invalid bank: {}", bank)),
+             })
+             .collect()
+    }
+
+    fn part1(banks: &Self::Input) -> Result<impl Display, String> {
+        Ok(banks.iter().map(|bank| find_largest_joltage(bank)).sum::<u64>())
+    }
+
+    fn part2(banks: &Self::Input) -> Result<impl Display, String> {
+        Ok(banks.iter().map(|bank| find_largest_joltage12(bank)).sum::<u64>())
+    }
+}
+
 
 #[cfg(test)]
 pub mod day3_tests {
     use super::*;
-    use std::fs;
 
 #[test]
 fn example1_1() {
@@ -70,18 +99,6 @@ fn example1_1() {
 }
 
 
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day3/input1.txt").unwrap();
-        let total = input.lines() // bank
-         .map(|line| {
-             find_largest_joltage(line)
-         })
-         .sum();
-    assert_eq!(17524u64, total);
-}
-
-
 #[test]
 fn example2_1() {
     let input = "987654321111111
@@ -103,18 +120,4 @@ fn example2_1() {
     assert_eq!(3121910778619u64, total);
 }
 
-
-#[test]
-fn part2() {
-    let input = fs::read_to_string("src/day3/input1.txt").unwrap();
-        let total = input.lines()
-         .map(|line| {
-             find_largest_joltage12(line)
-         })
-         .sum();
-    assert_ne!(17524u64, total);
-    assert_ne!(171959539665906u64, total); // too low
-    assert_eq!(173848577117276u64, total);
-}
-
 }
@@ -1,6 +1,9 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
+use aoc_utils::solution::Solution;
 
 
+pub struct Day4;
+
 
 fn count_accessible_papers(input: &str) -> usize {
     let paper_positions: Vec<(usize, usize)> = input.lines().enumerate()
@@ -102,10 +105,33 @@ fn count_accessible_papers_with_removal(input: &str) -> usize {
 }
 
 
+impl Solution for Day4 {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 4;
+    const INPUT: &'static str = "src/day4/input1.txt";
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        match input.lines().next() {
+            Some(_) => Ok(input.to_string()),
+            None => Err(String::from("empty grid")),
+        }
+    }
+
+    fn part1(grid: &Self::Input) -> Result<impl Display, String> {
+        Ok(count_accessible_papers(grid))
+    }
+
+    fn part2(grid: &Self::Input) -> Result<impl Display, String> {
+        Ok(count_accessible_papers_with_removal(grid))
+    }
+}
+
+
 #[cfg(test)]
 pub mod day4_tests {
     use super::*;
-    use std::fs;
 
 #[test]
 fn example1_1() {
@@ -136,14 +162,6 @@ fn example1_1() {
 }
 
 
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day4/input1.txt").unwrap();
-    let total = count_accessible_papers(input.as_str());
-    assert_eq!(1376, total);
-}
-
-
 #[test]
 fn example2_1() {
     let input = "..@@.@@@@.
@@ -162,14 +180,4 @@ fn example2_1() {
     assert_eq!(43, total);
 }
 
-
-#[test]
-fn part2() {
-    let input = fs::read_to_string("src/day4/input1.txt").unwrap();
-    let total = count_accessible_papers_with_removal(input.as_str());
-    assert_ne!(1376, total);
-    assert_eq!(8587, total);
-}
-
-
 }
@@ -1,8 +1,13 @@
-use std::{ops::RangeInclusive, str::FromStr};
+use std::{fmt::Display, ops::RangeInclusive, str::FromStr};
+use aoc_utils::intervals::IntervalSet;
+use aoc_utils::solution::Solution;
 
 
-struct Inventory {
-    fresh_ranges: Vec<RangeInclusive<i64>>,
+pub struct Day5;
+
+
+pub struct Inventory {
+    fresh_ranges: IntervalSet,
     available_ids: Vec<i64>,
 }
 
@@ -14,15 +19,14 @@ impl FromStr for Inventory {
         let fresh_section = sections.next().ok_or("Fresh section not found")?;
         let available_section = sections.next().ok_or("Available section not found")?;
 
-        let mut fresh_ranges: Vec<RangeInclusive<i64>> = fresh_section
-                                                            .lines()
-                                                            .filter_map(|line| {
-                                                                let (start, end) = line.split_once('-')?;
-                                                                Some((start.parse().ok()?, end.parse().ok()?))
-                                                            })
-                                                            .map(|(start, end)| RangeInclusive::new(start, end))
-                                                            .collect();
-        fresh_ranges.sort_by_key(|range| *range.start());
+        let fresh_ranges: IntervalSet = fresh_section
+                                        .lines()
+                                        .filter_map(|line| {
+                                            let (start, end) = line.split_once('-')?;
+                                            Some((start.parse().ok()?, end.parse().ok()?))
+                                        })
+                                        .map(|(start, end)| RangeInclusive::new(start, end))
+                                        .collect();
 
         let mut available_ids: Vec<i64> = available_section
             .lines()
@@ -39,50 +43,38 @@ impl FromStr for Inventory {
 
 
 fn check_ingredients(inventory: &Inventory) -> usize {
-    let mut fresh_count = 0;
-    let mut idx = 0;
-    for range in inventory.fresh_ranges.iter() {
-        while idx < inventory.available_ids.len() {
-            if inventory.available_ids[idx] > *range.end() {
-                break;
-            }
-            if range.contains(&inventory.available_ids[idx]) {
-                fresh_count += 1;
-            }
-            idx += 1;
-        }
-    }
-
-    fresh_count
+    inventory.available_ids.iter()
+                           .filter(|id| inventory.fresh_ranges.contains(**id))
+                           .count()
 }
 
 
 fn fresh_ingredients(inventory: &Inventory) -> i64 {
-    let mut count = 0;
-
-    let mut range = None;
-    for next_range in inventory.fresh_ranges.iter() {
-        match range {
-            None => {
-                range = Some(next_range.clone());
-            },
-            Some(cur_range) if *next_range.start() <= cur_range.end() + 1 => {
-                range = Some(RangeInclusive::new(*cur_range.start(), *cur_range.end().max(next_range.end())));
-            }
-            Some(cur_range) => {
-                count += cur_range.count() as i64;
-                range = Some(next_range.clone());
-            }
-        }
+    inventory.fresh_ranges.covered() as i64
+}
+
+
+impl Solution for Day5 {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 5;
+    const INPUT: &'static str = "src/day5/input1.txt";
+
+    type Input = Inventory;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        input.parse()
+    }
+
+    fn part1(inventory: &Self::Input) -> Result<impl Display, String> {
+        Ok(check_ingredients(inventory))
     }
-    if let Some(cur_range) = range {
-        count += cur_range.count() as i64;
+
+    fn part2(inventory: &Self::Input) -> Result<impl Display, String> {
+        Ok(fresh_ingredients(inventory))
     }
-    count
 }
 
 
-
 #[cfg(test)]
 pub mod day5_tests {
     use super::*;
@@ -116,15 +108,6 @@ fn example1_1() {
 }
 
 
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day5/input1.txt").unwrap();
-    let inventory: Inventory = input.parse().unwrap();
-    let total = check_ingredients(&inventory);
-    assert_ne!(3, total);
-    assert_eq!(726, total);
-}
-
 #[test]
 fn example2_1() {
     // A list of fresh ingredient ID ranges (inclusive), a blank line, and a list of available ingredient IDs
@@ -148,17 +131,6 @@ fn example2_1() {
 }
 
 
-#[test]
-fn part2() {
-    let input = fs::read_to_string("src/day5/input1.txt").unwrap();
-    let inventory: Inventory = input.parse().unwrap();
-    let total = fresh_ingredients(&inventory);
-    assert_ne!(3, total);
-    assert_ne!(726, total);
-    assert_eq!(354226555270043, total);
-}
-
-
 #[test]
 fn part3() {
     let input = fs::read_to_string("src/day5/aoc-2025-day-5-challenge-1.txt").unwrap();
@@ -169,5 +141,4 @@ fn part3() {
     assert_eq!(498078480804022, total);
 }
 
-
 }
@@ -1,5 +1,9 @@
 use core::num;
-use std::{collections::HashMap, str::{Chars, FromStr}};
+use std::{collections::HashMap, fmt::Display, str::{Chars, FromStr}};
+use aoc_utils::solution::Solution;
+
+
+pub struct Day6;
 
 
 struct MathWorksheet {
@@ -121,11 +125,34 @@ fn perform_calculation(worksheet: &MathWorksheet) -> u64 {
 }
 
 
+impl Solution for Day6 {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 6;
+    const INPUT: &'static str = "src/day6/input1.txt";
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        match input.lines().last() {
+            Some(operations) if operations.chars().all(|c| c == '+' || c == '*' || c.is_whitespace()) => Ok(input.to_string()),
+            _ => Err(String::from("missing operations")),
+        }
+    }
+
+    fn part1(worksheet: &Self::Input) -> Result<impl Display, String> {
+        let worksheet: MathWorksheet = worksheet.parse().map_err(|_| "invalid worksheet")?;
+        Ok(perform_calculation(&worksheet))
+    }
+
+    fn part2(worksheet: &Self::Input) -> Result<impl Display, String> {
+        Ok(MathWorksheet::parse_and_calculate(worksheet))
+    }
+}
+
 
 #[cfg(test)]
 pub mod day6_tests {
     use super::*;
-    use std::fs;
 
 #[test]
 fn example1_1() {
@@ -144,16 +171,6 @@ fn example1_1() {
 }
 
 
-#[test]
-fn part1() {
-    let input = fs::read_to_string("src/day6/input1.txt").unwrap();
-    let worksheet: MathWorksheet = input.parse().unwrap();
-    let total = perform_calculation(&worksheet);
-    assert_ne!(4277556, total);
-    assert_eq!(4693159084994, total);
-}
-
-
 #[test]
 fn example2_1() {
     let input = 
@@ -172,15 +189,4 @@ fn example2_1() {
     assert_eq!(3263827, total);
 }
 
-
-#[test]
-fn part2() {
-    let input = fs::read_to_string("src/day6/input1.txt").unwrap();
-    let total = MathWorksheet::parse_and_calculate(input.as_str());
-    assert_ne!(4277556, total);
-    assert_ne!(4693159084994, total);
-    assert_eq!(11643736116335, total);
-}
-
-
 }
@@ -1,4 +1,6 @@
-// pub mod utils;
+use std::process::ExitCode;
+
+pub mod runner;
 
 pub mod day1;
 pub mod day2;
@@ -16,4 +18,7 @@ pub mod day6;
 // pub mod day14;
 // pub mod day15;
 
-fn main() {}
+
+fn main() -> ExitCode {
+    aoc_utils::cli::main("AoC2025", runner::YEAR, runner::registry())
+}
@@ -0,0 +1,39 @@
+use aoc_utils::solution::Registry;
+use crate::*;
+
+
+pub const YEAR: u16 = 2025;
+
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::default();
+    registry.register::<day1::Day1>();
+    registry.register::<day2::Day2>();
+    registry.register::<day3::Day3>();
+    registry.register::<day4::Day4>();
+    registry.register::<day5::Day5>();
+    registry.register::<day6::Day6>();
+    registry
+}
+
+
+#[cfg(test)]
+pub mod runner_tests {
+    use super::*;
+    use aoc_utils::answers::{self, Answers, Status};
+
+// day and part that have no accepted answer yet, every other one must pass
+const UNSOLVED: &[(u8, u8)] = &[];
+
+
+#[test]
+fn verify_answers() {
+    let answers: Answers = std::fs::read_to_string("answers.txt").unwrap().parse().unwrap();
+    let failed: Vec<String> = answers::verify(&registry(), YEAR, &answers).iter()
+                                      .filter(|v| v.status != Status::PASS && !UNSOLVED.contains(&(v.day, v.part)))
+                                      .map(|v| format!("day {} part {}: {} ({})", v.day, v.part, v.answer(), v.note()))
+                                      .collect();
+    assert!(failed.is_empty(), "answers not accepted: {:#?}", failed);
+}
+
+}
@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use std::str::FromStr;
+
+use crate::solution::{Puzzle, Registry};
+
+
+/// The answers known for one part: the accepted one (if any) and the ones rejected before it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub accepted: Option<String>,
+    pub wrong: Vec<String>,
+}
+
+
+/// The answers file of a year, one line per answer:
+///
+/// ```text
+/// # day.part = accepted answer, day.part != known-wrong answer
+/// 7.1 = 303766880536
+/// 7.1 != 303766878186
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    expected: BTreeMap<(u8, u8), Expected>,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    PASS,
+    FAIL,
+    REGRESSED,
+}
+
+
+pub struct Verification {
+    pub day: u8,
+    pub part: u8,
+    pub result: Result<String, String>,
+    pub expected: Expected,
+    pub status: Status,
+}
+
+
+fn parse_key(key: &str) -> Option<(u8, u8)> {
+    let (day, part) = key.split_once('.')?;
+    Some((day.parse().ok()?, part.parse().ok()?))
+}
+
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+
+        for (nr, line) in s.lines().enumerate().map(|(nr, line)| (nr + 1, line.trim())) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, accepted, answer) = if let Some((key, answer)) = line.split_once("!=") {
+                (key, false, answer)
+            } else if let Some((key, answer)) = line.split_once('=') {
+                (key, true, answer)
+            } else {
+                return Err(format!("line {}: expected '<day>.<part> = <answer>' or '<day>.<part> != <answer>'", nr));
+            };
+
+            let key = parse_key(key.trim()).ok_or(format!("line {}: invalid day.part: {}", nr, key.trim()))?;
+            let answer = answer.trim().to_string();
+            if answer.is_empty() {
+                return Err(format!("line {}: missing answer", nr));
+            }
+
+            let expected = answers.expected.entry(key).or_default();
+            if !accepted {
+                expected.wrong.push(answer);
+            } else if expected.accepted.is_some() {
+                return Err(format!("line {}: second accepted answer for {}.{}", nr, key.0, key.1));
+            } else {
+                expected.accepted = Some(answer);
+            }
+        }
+        Ok(answers)
+    }
+}
+
+
+impl Answers {
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Expected> {
+        self.expected.get(&(day, part))
+    }
+
+}
+
+
+impl Expected {
+
+    pub fn check(&self, result: &Result<String, String>) -> Status {
+        match (&self.accepted, result) {
+            (Some(accepted), Ok(answer)) if accepted == answer => Status::PASS,
+            (Some(_), _) => Status::REGRESSED,
+            (None, _) => Status::FAIL,
+        }
+    }
+
+}
+
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::PASS => f.write_str("pass"),
+            Status::FAIL => f.write_str("fail"),
+            Status::REGRESSED => f.write_str("regressed"),
+        }
+    }
+}
+
+
+impl Verification {
+
+    pub fn answer(&self) -> &str {
+        match &self.result {
+            Ok(answer) => answer,
+            Err(message) => message,
+        }
+    }
+
+    pub fn note(&self) -> String {
+        match (&self.result, &self.expected.accepted) {
+            (Ok(answer), _) if self.expected.wrong.contains(answer) => String::from("known wrong"),
+            (_, Some(accepted)) if self.status == Status::REGRESSED => format!("expected {}", accepted),
+            (Ok(_), None) => String::from("no accepted answer"),
+            _ => String::new(),
+        }
+    }
+
+}
+
+
+fn solve_puzzle(puzzle: &Puzzle) -> [Result<String, String>; 2] {
+    let parsed = fs::read_to_string(puzzle.input)
+                    .map_err(|e| format!("cannot read {}: {}", puzzle.input, e))
+                    .and_then(|input| puzzle.parse(&input));
+    [1, 2].map(|part| parsed.as_ref()
+                            .map_err(|e| e.clone())
+                            .and_then(|parsed| parsed.solve(part)))
+}
+
+
+/// Runs both parts of every registered puzzle of `year` and checks the answers against the answers file.
+pub fn verify(registry: &Registry, year: u16, answers: &Answers) -> Vec<Verification> {
+    let mut verifications = Vec::new();
+    for puzzle in registry.iter().filter(|puzzle| puzzle.year == year) {
+        for (part, result) in (1..).zip(solve_puzzle(puzzle)) {
+            let expected = answers.get(puzzle.day, part).cloned().unwrap_or_default();
+            verifications.push(Verification {
+                day: puzzle.day,
+                part,
+                status: expected.check(&result),
+                result,
+                expected,
+            });
+        }
+    }
+    verifications
+}
+
+
+
+#[cfg(test)]
+pub mod answers_tests {
+    use super::*;
+
+#[test]
+fn parse() {
+    let input =
+"# 2024
+7.1 = 303766880536
+7.1 != 303766878186
+
+8.1 != 380
+8.1 != 371";
+
+    let answers = Answers::from_str(input).unwrap();
+    assert_eq!(Some(&String::from("303766880536")), answers.get(7, 1).unwrap().accepted.as_ref());
+    assert_eq!(vec![String::from("303766878186")], answers.get(7, 1).unwrap().wrong);
+    assert_eq!(None, answers.get(8, 1).unwrap().accepted);
+    assert_eq!(2, answers.get(8, 1).unwrap().wrong.len());
+    assert!(answers.get(7, 2).is_none());
+}
+
+
+#[test]
+fn parse_errors() {
+    assert_eq!(Err(String::from("line 1: invalid day.part: 7")), Answers::from_str("7 = 1").map(|_| ()));
+    assert_eq!(Err(String::from("line 2: missing answer")), Answers::from_str("7.1 = 1\n7.2 =").map(|_| ()));
+    assert_eq!(Err(String::from("line 2: second accepted answer for 7.1")), Answers::from_str("7.1 = 1\n7.1 = 2").map(|_| ()));
+}
+
+
+#[test]
+fn check() {
+    let expected = Expected { accepted: Some(String::from("42")), wrong: vec![String::from("41")] };
+    assert_eq!(Status::PASS, expected.check(&Ok(String::from("42"))));
+    assert_eq!(Status::REGRESSED, expected.check(&Ok(String::from("41"))));
+    assert_eq!(Status::REGRESSED, expected.check(&Err(String::from("not solved"))));
+    assert_eq!(Status::FAIL, Expected::default().check(&Ok(String::from("42"))));
+    assert_eq!(Status::FAIL, Expected::default().check(&Err(String::from("not solved"))));
+}
+
+}
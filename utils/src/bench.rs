@@ -0,0 +1,154 @@
+use std::fmt::Write;
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::solution::{Parsed, Puzzle};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+
+/// The timings of one puzzle: the input is parsed once, each part is solved `runs` times on it.
+#[derive(Debug)]
+pub struct Benchmark {
+    pub year: u16,
+    pub day: u8,
+    pub parse: Duration,
+    pub parts: [Result<Timings, String>; 2],
+}
+
+
+impl Timings {
+
+    pub fn from(mut samples: Vec<Duration>) -> Option<Timings> {
+        samples.sort();
+        Some(Timings {
+            runs: samples.len(),
+            min: *samples.first()?,
+            median: samples[samples.len() / 2],
+            max: *samples.last()?,
+        })
+    }
+
+}
+
+
+fn time_part(parsed: &dyn Parsed, part: u8, runs: usize) -> Result<Timings, String> {
+    let mut samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        parsed.solve(part)?;
+        samples.push(start.elapsed());
+    }
+    Timings::from(samples).ok_or(String::from("no runs"))
+}
+
+
+pub fn bench(puzzle: &Puzzle, runs: usize) -> Result<Benchmark, String> {
+    let input = fs::read_to_string(puzzle.input).map_err(|e| format!("cannot read {}: {}", puzzle.input, e))?;
+
+    let start = Instant::now();
+    let parsed = puzzle.parse(&input)?;
+    let parse = start.elapsed();
+
+    Ok(Benchmark {
+        year: puzzle.year,
+        day: puzzle.day,
+        parse,
+        parts: [1, 2].map(|part| time_part(parsed.as_ref(), part, runs)),
+    })
+}
+
+
+// one row per stage: the parse time is a single measurement, unsolved parts are left out
+fn rows(benchmark: &Benchmark) -> Vec<(String, Timings)> {
+    let parse = Timings { runs: 1, min: benchmark.parse, median: benchmark.parse, max: benchmark.parse };
+    let mut rows = vec![(String::from("parse"), parse)];
+    for (part, timings) in (1..).zip(benchmark.parts.iter()) {
+        if let Ok(timings) = timings {
+            rows.push((format!("part{}", part), *timings));
+        }
+    }
+    rows
+}
+
+
+pub fn to_csv(benchmarks: &[Benchmark]) -> String {
+    let mut csv = String::from("year,day,stage,runs,min_ns,median_ns,max_ns\n");
+    for benchmark in benchmarks {
+        for (stage, t) in rows(benchmark) {
+            writeln!(csv, "{},{},{},{},{},{},{}", benchmark.year, benchmark.day, stage, t.runs,
+                     t.min.as_nanos(), t.median.as_nanos(), t.max.as_nanos()).unwrap();
+        }
+    }
+    csv
+}
+
+
+pub fn to_json(benchmarks: &[Benchmark]) -> String {
+    let entries: Vec<String> = benchmarks.iter()
+        .flat_map(|benchmark| rows(benchmark).into_iter().map(move |(stage, t)| {
+            format!("  {{\"year\": {}, \"day\": {}, \"stage\": \"{}\", \"runs\": {}, \"min_ns\": {}, \"median_ns\": {}, \"max_ns\": {}}}",
+                    benchmark.year, benchmark.day, stage, t.runs, t.min.as_nanos(), t.median.as_nanos(), t.max.as_nanos())
+        }))
+        .collect();
+    format!("[\n{}\n]\n", entries.join(",\n"))
+}
+
+
+
+#[cfg(test)]
+pub mod bench_tests {
+    use super::*;
+
+fn example() -> Vec<Benchmark> {
+    vec![Benchmark {
+        year: 2024,
+        day: 7,
+        parse: Duration::from_nanos(1500),
+        parts: [
+            Timings::from(vec![Duration::from_nanos(30), Duration::from_nanos(10), Duration::from_nanos(20)]).ok_or(String::new()),
+            Err(String::from("not solved")),
+        ],
+    }]
+}
+
+
+#[test]
+fn timings() {
+    let timings = Timings::from(vec![Duration::from_millis(5), Duration::from_millis(1), Duration::from_millis(9), Duration::from_millis(2)]).unwrap();
+    assert_eq!(4, timings.runs);
+    assert_eq!(Duration::from_millis(1), timings.min);
+    assert_eq!(Duration::from_millis(5), timings.median);
+    assert_eq!(Duration::from_millis(9), timings.max);
+    assert_eq!(None, Timings::from(Vec::new()));
+}
+
+
+#[test]
+fn csv() {
+    assert_eq!(
+"year,day,stage,runs,min_ns,median_ns,max_ns
+2024,7,parse,1,1500,1500,1500
+2024,7,part1,3,10,20,30
+", to_csv(&example()));
+}
+
+
+#[test]
+fn json() {
+    assert_eq!(
+r#"[
+  {"year": 2024, "day": 7, "stage": "parse", "runs": 1, "min_ns": 1500, "median_ns": 1500, "max_ns": 1500},
+  {"year": 2024, "day": 7, "stage": "part1", "runs": 3, "min_ns": 10, "median_ns": 20, "max_ns": 30}
+]
+"#, to_json(&example()));
+}
+
+}
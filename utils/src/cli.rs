@@ -0,0 +1,196 @@
+use std::{collections::HashMap, env, fs, path::Path, process::ExitCode, time::{Duration, Instant}};
+
+use crate::answers::{self, Answers, Status};
+use crate::bench::{self, Benchmark};
+use crate::solution::Registry;
+
+
+const ANSWERS: &str = "answers.txt";
+const BENCH_RUNS: usize = 10;
+const BENCH_REPORT: &str = "target/bench.csv";
+
+
+struct RunArgs {
+    day: u8,
+    part: u8,
+    input: Option<String>,
+}
+
+
+struct VerifyArgs {
+    answers: String,
+}
+
+
+struct BenchArgs {
+    day: Option<u8>,
+    runs: usize,
+    report: String,
+}
+
+
+fn usage(binary: &str) -> String {
+    [
+        format!("usage: {} run --day <day> --part <1|2> [--input <path>]", binary),
+        format!("       {} verify [--answers <path>]", binary),
+        format!("       {} bench [--day <day>] [--runs <n>] [--report <path.csv|path.json>]", binary),
+    ].join("\n")
+}
+
+
+fn parse_options<'a>(args: &'a [String], names: &[&str]) -> Result<HashMap<&'a str, &'a str>, String> {
+    let mut options = HashMap::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or(format!("missing value for {}", arg))?;
+        match arg.strip_prefix("--") {
+            Some(name) if names.contains(&name) => options.insert(name, value.as_str()),
+            _ => return Err(format!("unknown option: {}", arg)),
+        };
+    }
+    Ok(options)
+}
+
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let options = parse_options(args, &["day", "part", "input"])?;
+    let day = options.get("day").ok_or("missing --day")?;
+    let part = options.get("part").ok_or("missing --part")?;
+
+    Ok(RunArgs {
+        day: day.parse().map_err(|_| format!("invalid day: {}", day))?,
+        part: part.parse().map_err(|_| format!("invalid part: {}", part))?,
+        input: options.get("input").map(|input| input.to_string()),
+    })
+}
+
+
+fn parse_verify_args(args: &[String]) -> Result<VerifyArgs, String> {
+    let options = parse_options(args, &["answers"])?;
+    Ok(VerifyArgs {
+        answers: options.get("answers").unwrap_or(&ANSWERS).to_string(),
+    })
+}
+
+
+fn parse_bench_args(args: &[String]) -> Result<BenchArgs, String> {
+    let options = parse_options(args, &["day", "runs", "report"])?;
+    let day = options.get("day")
+                     .map(|day| day.parse().map_err(|_| format!("invalid day: {}", day)))
+                     .transpose()?;
+    let runs = options.get("runs")
+                      .map(|runs| runs.parse().ok().filter(|runs| *runs > 0).ok_or(format!("invalid runs: {}", runs)))
+                      .transpose()?;
+
+    Ok(BenchArgs {
+        day,
+        runs: runs.unwrap_or(BENCH_RUNS),
+        report: options.get("report").unwrap_or(&BENCH_REPORT).to_string(),
+    })
+}
+
+
+fn run(year: u16, registry: &Registry, args: RunArgs) -> Result<(), String> {
+    let puzzle = registry.get(year, args.day).ok_or(format!("unknown puzzle: day {}", args.day))?;
+    let path = args.input.unwrap_or_else(|| puzzle.input.to_string());
+    let input = fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path, e))?;
+
+    let start = Instant::now();
+    let answer = puzzle.parse(&input)
+                       .map_err(|e| format!("cannot parse {}: {}", path, e))?
+                       .solve(args.part)
+                       .map_err(|e| format!("day {} part {}: {}", args.day, args.part, e))?;
+    let elapsed = start.elapsed();
+
+    println!("day {} part {}: {}", args.day, args.part, answer);
+    println!("elapsed: {:?}", elapsed);
+    Ok(())
+}
+
+
+fn verify(year: u16, registry: &Registry, args: VerifyArgs) -> Result<(), String> {
+    let answers: Answers = fs::read_to_string(&args.answers)
+                              .map_err(|e| format!("cannot read {}: {}", args.answers, e))?
+                              .parse()
+                              .map_err(|e| format!("cannot parse {}: {}", args.answers, e))?;
+    let verifications = answers::verify(registry, year, &answers);
+
+    println!("{:>3} {:>4}  {:<9}  {:<20}  note", "day", "part", "status", "answer");
+    for v in verifications.iter() {
+        println!("{:>3} {:>4}  {:<9}  {:<20}  {}", v.day, v.part, v.status.to_string(), v.answer(), v.note());
+    }
+
+    let count = |status| verifications.iter().filter(|v| v.status == status).count();
+    println!("\n{} passed, {} failed, {} regressed", count(Status::PASS), count(Status::FAIL), count(Status::REGRESSED));
+    match count(Status::REGRESSED) {
+        0 => Ok(()),
+        regressed => Err(format!("{} answers regressed", regressed)),
+    }
+}
+
+
+fn print_benchmark(benchmark: &Benchmark) {
+    let format = |duration: Duration| format!("{:.1?}", duration);
+    println!("{:>3}  {:<6} {:>5}  {:>10}", benchmark.day, "parse", 1, format(benchmark.parse));
+    for (part, timings) in (1..).zip(benchmark.parts.iter()) {
+        match timings {
+            Ok(t) => println!("{:>3}  {:<6} {:>5}  {:>10}  {:>10}  {:>10}", benchmark.day, format!("part{}", part), t.runs,
+                              format(t.min), format(t.median), format(t.max)),
+            Err(message) => println!("{:>3}  {:<6} {:>5}  {}", benchmark.day, format!("part{}", part), 0, message),
+        }
+    }
+}
+
+
+fn bench(year: u16, registry: &Registry, args: BenchArgs) -> Result<(), String> {
+    let puzzles: Vec<_> = registry.iter()
+                                  .filter(|puzzle| puzzle.year == year && args.day.is_none_or(|day| day == puzzle.day))
+                                  .collect();
+    if puzzles.is_empty() {
+        return Err(String::from("no puzzles to benchmark"));
+    }
+
+    println!("{:>3}  {:<6} {:>5}  {:>10}  {:>10}  {:>10}", "day", "stage", "runs", "min", "median", "max");
+    let mut benchmarks = Vec::new();
+    for puzzle in puzzles {
+        match bench::bench(puzzle, args.runs) {
+            Ok(benchmark) => {
+                print_benchmark(&benchmark);
+                benchmarks.push(benchmark);
+            },
+            Err(message) => println!("{:>3}  {}", puzzle.day, message),
+        }
+    }
+
+    let report = match Path::new(&args.report).extension().and_then(|e| e.to_str()) {
+        Some("json") => bench::to_json(&benchmarks),
+        _ => bench::to_csv(&benchmarks),
+    };
+    if let Some(dir) = Path::new(&args.report).parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+    }
+    fs::write(&args.report, report).map_err(|e| format!("cannot write {}: {}", args.report, e))?;
+    println!("\nreport written to {}", args.report);
+    Ok(())
+}
+
+
+/// Entry point shared by the year binaries: dispatches the command line against the puzzles of one year.
+pub fn main(binary: &str, year: u16, registry: Registry) -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.split_first() {
+        Some((command, options)) if command == "run" => parse_run_args(options).and_then(|args| run(year, &registry, args)),
+        Some((command, options)) if command == "verify" => parse_verify_args(options).and_then(|args| verify(year, &registry, args)),
+        Some((command, options)) if command == "bench" => parse_bench_args(options).and_then(|args| bench(year, &registry, args)),
+        _ => Err(usage(binary)),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
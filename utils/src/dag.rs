@@ -0,0 +1,226 @@
+use std::collections::HashSet;
+
+use crate::grid::Grid;
+use crate::position::Position;
+
+
+/// The directed graph a grid makes when a cell leads to those direct sides it may step to.
+/// The steps must never lead back to where they started, so the cells can be handled in topological order.
+pub struct GridDag<'g, T> {
+    grid: &'g Grid<T>,
+    successors: Vec<Vec<Position>>,
+    order: Vec<Position>,
+}
+
+
+impl<'g, T> GridDag<'g, T> {
+
+    /// A cell leads to each direct side for which `step` holds on the values of both; fails when the steps form a cycle.
+    pub fn new<S>(grid: &'g Grid<T>, step: S) -> Result<Self, String>
+        where S: Fn(&T, &T) -> bool
+    {
+        let successors: Vec<Vec<Position>> =
+            grid.iter_lr_down()
+                .map(|from| from.sides().into_iter()
+                                .flatten()
+                                .filter(|to| grid.at(to).is_some_and(|value| step(&grid[from], value)))
+                                .collect())
+                .collect();
+
+        // Kahn: a cell is next once every cell leading to it has been ordered
+        let mut incoming = vec![0usize; successors.len()];
+        successors.iter().flatten().for_each(|to| incoming[to.to_index(grid)] += 1);
+        let mut order: Vec<Position> = grid.iter_lr_down().filter(|cell| incoming[cell.to_index(grid)] == 0).collect();
+        let mut next = 0;
+        while next < order.len() {
+            for to in successors[order[next].to_index(grid)].iter() {
+                let index = to.to_index(grid);
+                incoming[index] -= 1;
+                if incoming[index] == 0 {
+                    order.push(*to);
+                }
+            }
+            next += 1;
+        }
+
+        if let Some(index) = incoming.iter().position(|count| *count > 0) {
+            return Err(format!("the steps form a cycle through {}", grid.position_of(index)));
+        }
+        Ok(GridDag { grid, successors, order })
+    }
+
+    pub fn successors(&self, position: &Position) -> &[Position] {
+        &self.successors[position.to_index(self.grid)]
+    }
+
+    /// Every cell, each one before the cells it leads to.
+    pub fn topological_order(&self) -> &[Position] {
+        &self.order
+    }
+
+    /// For every cell, the sinks it can reach, itself included when it is one.
+    pub fn reachable_sinks<P>(&self, is_sink: P) -> Grid<HashSet<Position>>
+        where P: Fn(&T) -> bool
+    {
+        let mut reachable = vec![HashSet::new(); self.successors.len()];
+        for from in self.order.iter().rev() {
+            let mut sinks: HashSet<Position> = self.successors(from).iter()
+                                                   .flat_map(|to| reachable[to.to_index(self.grid)].iter().copied())
+                                                   .collect();
+            if is_sink(&self.grid[*from]) {
+                sinks.insert(*from);
+            }
+            reachable[from.to_index(self.grid)] = sinks;
+        }
+        Grid { grid: reachable, width: self.grid.width, height: self.grid.height }
+    }
+
+    /// For every cell, the number of distinct paths from it that end in a sink.
+    /// Fails when a count does not fit in 64 bits, path counts can grow exponentially with the size of the grid.
+    pub fn path_counts<P>(&self, is_sink: P) -> Result<Grid<u64>, String>
+        where P: Fn(&T) -> bool
+    {
+        let mut counts = vec![0u64; self.successors.len()];
+        for from in self.order.iter().rev() {
+            let count = self.successors(from).iter()
+                            .try_fold(u64::from(is_sink(&self.grid[*from])), |count, to| count.checked_add(counts[to.to_index(self.grid)]))
+                            .ok_or(format!("too many paths from {} to count", from))?;
+            counts[from.to_index(self.grid)] = count;
+        }
+        Ok(Grid { grid: counts, width: self.grid.width, height: self.grid.height })
+    }
+
+    /// The paths from the start that end in a sink, found one at a time as the iterator is advanced.
+    /// There are none from a start outside the grid.
+    pub fn paths<P>(&self, start: Position, is_sink: P) -> DagPaths<'_, 'g, T, P>
+        where P: Fn(&T) -> bool
+    {
+        let inside = self.grid.in_grid(&start);
+        DagPaths { dag: self, is_sink, path: inside.then_some(start).into_iter().collect(), branches: vec![0], arrived: true }
+    }
+
+}
+
+
+/// Walks the paths of a [GridDag] depth first, see [GridDag::paths].
+pub struct DagPaths<'d, 'g, T, P> {
+    dag: &'d GridDag<'g, T>,
+    is_sink: P,
+    path: Vec<Position>,
+    // for every cell on the path, the successor to try next
+    branches: Vec<usize>,
+    // the last cell of the path has not been checked for being a sink yet
+    arrived: bool,
+}
+
+
+impl<T, P> Iterator for DagPaths<'_, '_, T, P>
+    where P: Fn(&T) -> bool
+{
+    type Item = Vec<Position>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let last = *self.path.last()?;
+            if self.arrived {
+                self.arrived = false;
+                if (self.is_sink)(&self.dag.grid[last]) {
+                    return Some(self.path.clone());
+                }
+            }
+            let branch = self.branches.last_mut()?;
+            match self.dag.successors(&last).get(*branch) {
+                Some(next) => {
+                    *branch += 1;
+                    self.path.push(*next);
+                    self.branches.push(0);
+                    self.arrived = true;
+                },
+                None => {
+                    self.path.pop();
+                    self.branches.pop();
+                },
+            }
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+pub mod dag_tests {
+    use super::*;
+    use std::str::FromStr;
+
+
+fn downhill(from: &char, to: &char) -> bool {
+    to < from
+}
+
+
+#[test]
+fn diamond() {
+    // c leads to both b's, each b to a
+    let grid: Grid<char> = Grid::from_str("cb\nba").unwrap();
+    let dag = GridDag::new(&grid, downhill).unwrap();
+    assert_eq!(Position { x: 0, y: 0 }, dag.topological_order()[0]);
+    assert_eq!(Position { x: 1, y: 1 }, dag.topological_order()[3]);
+
+    let counts = dag.path_counts(|c| *c == 'a').unwrap();
+    assert_eq!(vec![2, 1, 1, 1], counts.grid);
+    let sinks = dag.reachable_sinks(|c| *c == 'a');
+    assert_eq!(HashSet::from([Position { x: 1, y: 1 }]), sinks[Position { x: 0, y: 0 }]);
+
+    let paths: Vec<Vec<Position>> = dag.paths(Position { x: 0, y: 0 }, |c| *c == 'a').collect();
+    assert_eq!(vec![vec![Position { x: 0, y: 0 }, Position { x: 1, y: 0 }, Position { x: 1, y: 1 }],
+                    vec![Position { x: 0, y: 0 }, Position { x: 0, y: 1 }, Position { x: 1, y: 1 }]], paths);
+}
+
+
+#[test]
+fn sinks_along_the_way() {
+    // the sink b is halfway down, a below it reaches none; with every cell a sink, each prefix is a path
+    let grid: Grid<char> = Grid::from_str("dcba").unwrap();
+    let dag = GridDag::new(&grid, downhill).unwrap();
+    assert_eq!(vec![1, 1, 1, 0], dag.path_counts(|c| *c == 'b').unwrap().grid);
+    assert_eq!(vec![4, 3, 2, 1], dag.path_counts(|_| true).unwrap().grid);
+    assert_eq!(vec![1, 2, 3, 4], dag.paths(Position { x: 0, y: 0 }, |_| true).map(|path| path.len()).collect::<Vec<_>>());
+}
+
+
+#[test]
+fn lazy_paths() {
+    // every step goes right or down, C(38, 19) ways across, far too many to collect
+    let grid: Grid<u8> = Grid { grid: (0..400).map(|i| (i % 20 + i / 20) as u8).collect(), width: 20, height: 20 };
+    let dag = GridDag::new(&grid, |from, to| *to == from + 1).unwrap();
+    let corner = |value: &u8| *value == 38;
+    assert_eq!(35_345_263_800, dag.path_counts(corner).unwrap()[Position { x: 0, y: 0 }]);
+    let first: Vec<Vec<Position>> = dag.paths(Position { x: 0, y: 0 }, corner).take(3).collect();
+    assert_eq!(3, first.len());
+    assert!(first.iter().all(|path| path.len() == 39 && path[38] == Position { x: 19, y: 19 }));
+    assert_eq!(0, dag.paths(Position { x: 20, y: 0 }, corner).count());
+}
+
+
+#[test]
+fn too_many_paths() {
+    // C(68, 34) paths across 35x35 cells do not fit in 64 bits, C(66, 33) across 34x34 do
+    let across = |size: usize| {
+        let grid: Grid<u8> = Grid { grid: (0..size * size).map(|i| (i % size + i / size) as u8).collect(), width: size, height: size };
+        let corner = 2 * (size as u8 - 1);
+        GridDag::new(&grid, |from, to| *to == from + 1).unwrap()
+                                                       .path_counts(|value| *value == corner)
+                                                       .map(|counts| counts[Position { x: 0, y: 0 }])
+    };
+    assert_eq!(Ok(7_219_428_434_016_265_740), across(34));
+    assert!(across(35).is_err());
+}
+
+
+#[test]
+fn cycle() {
+    let grid: Grid<char> = Grid::from_str("aa").unwrap();
+    assert!(GridDag::new(&grid, |a, b| a == b).is_err());
+}
+
+}
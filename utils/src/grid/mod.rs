@@ -0,0 +1,294 @@
+use std::fmt::Display;
+use std::ops::Index;
+use std::str::FromStr;
+
+use super::position::Position;
+use super::Move;
+
+pub mod recorder;
+pub mod render;
+pub mod svg;
+pub mod torus;
+
+#[derive(Debug, Clone)]
+pub struct Grid<T> {
+    pub grid: Vec<T>,
+    pub width: usize,
+    pub height: usize,
+}
+
+
+/// Where and why a grid could not be parsed; `line` and `column` start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGridError {
+    pub line: usize,
+    pub column: usize,
+    pub reason: String,
+}
+
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.reason)
+    }
+}
+
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = ParseGridError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut grid = Vec::new();
+        let mut width: Option<usize> = None;
+        let mut height = 0;
+
+        for (line, row) in input.lines().enumerate().map(|(nr, row)| (nr + 1, row)) {
+            let mut row_width = 0;
+            for (column, cell) in row.chars().enumerate().map(|(nr, cell)| (nr + 1, cell)) {
+                let value = T::try_from(cell).map_err(|_| ParseGridError {
+                    line,
+                    column,
+                    reason: format!("invalid cell '{}'", cell),
+                })?;
+                grid.push(value);
+                row_width += 1;
+            }
+
+            match width {
+                Some(expected) if expected != row_width => return Err(ParseGridError {
+                    line,
+                    column: expected.min(row_width) + 1,
+                    reason: format!("row has width {}, expected {}", row_width, expected),
+                }),
+                Some(_) => {},
+                None => width = Some(row_width),
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            grid,
+            width: width.unwrap_or_default(),
+            height,
+        })
+    }
+
+}
+
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.grid.chunks(self.width.max(1)) {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        &self.grid[position.to_index(self)]
+    }
+}
+
+
+
+impl<T: Clone> Grid<T> {
+
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            grid: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+}
+
+
+impl<T> Grid<T> {
+
+    pub fn find_all_positions<P>(&self, predicate: P) -> Vec<Position>
+        where P: Fn(&T) -> bool
+    {
+        self.grid.iter().enumerate()
+                 .filter(|(_idx, val)| predicate(val))
+                 .map(|(idx, _)| self.position_of(idx))
+                 .collect()
+    }
+
+
+    pub fn distances(&self, position: &Position) -> (usize, usize, usize, usize) {
+        (
+            position.x, // to left
+            self.width - position.x, // to right
+            position.y, // to top
+            self.height - position.y, // to bottom
+        )
+    }
+
+    pub fn position_of(&self, index: usize) -> Position {
+        Position::from(index, self)
+    }
+
+    pub fn at(&self, position: &Position) -> Option<&T> {
+        if !self.in_grid(position) {
+            return None;
+        }
+        self.grid.get(position.to_index(self))
+    }
+
+    pub fn in_grid(&self, position: &Position) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn max_to(&self, position: &Position, direction: &Move) -> usize {
+        match direction {
+            Move::TOP => position.y,
+            Move::TOP_RIGHT => position.y.min(self.distances(position).1),
+            Move::RIGHT => self.distances(position).1,
+            Move::DOWN_RIGHT => {
+                let distances = self.distances(position);
+                distances.1.min(distances.3)
+            },
+            Move::DOWN => self.distances(position).3,
+            Move::DOWN_LEFT => {
+                let distances = self.distances(position);
+                distances.0.min(distances.3)
+            },
+            Move::LEFT => self.distances(position).0,
+            Move::TOP_LEFT => {
+                let distances = self.distances(position);
+                distances.0.min(distances.2)
+            },
+        }
+    }
+
+
+    pub fn positions_to(&self, position: &Position, direction: &Move, size: usize) -> Vec<Position> {
+        let mut result = Vec::new();
+
+        for add in 1..size {
+            if let Some(y) = direction.move_y(position.y, add) {
+                if let Some(x) = direction.move_x(position.x, add) {
+                    result.push( Position{x, y} );
+                }
+            }
+        }
+        result.retain(|pos| pos.x < self.width && pos.y <= self.height);
+        result
+    }
+
+
+    pub fn positions_to_inclusive(&self, position: &Position, direction: &Move, size: usize) -> Vec<Position> {
+        let mut result = Vec::new();
+
+        for add in 0..size {
+            if let Some(y) = direction.move_y(position.y, add) {
+                if let Some(x) = direction.move_x(position.x, add) {
+                    result.push( Position{x, y} );
+                }
+            }
+        }
+
+        result.retain(|pos| pos.x < self.width && pos.y <= self.height);
+        result
+    }
+
+
+    pub fn set(&mut self, position: &Position, value: T) {
+        let idx = position.to_index(self);
+        if idx < self.grid.len() {
+            self.grid[idx] = value;
+        }
+    }
+
+    pub fn iter_lr_down(&self) -> impl Iterator<Item = Position> + use<'_, T> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| Position{x, y}))
+    }
+
+    // fn walk_to(&self, position: &Position, direction: &Move) -> impl Iterator<Item = (&Position, Option<&T>)> {
+    //     self.positions_to(position, direction, self.max_to(position, direction))
+    //         .iter()
+    //         .map(|pos| (pos, self.at(pos)))
+    // }
+
+}
+
+
+
+#[cfg(test)]
+pub mod grid_tests {
+    use super::*;
+
+#[derive(Debug, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+enum Tile {
+    WALL,
+    EMPTY,
+}
+
+
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '#' => Ok(Tile::WALL),
+            '.' => Ok(Tile::EMPTY),
+            _ => Err(c),
+        }
+    }
+}
+
+
+#[test]
+fn display() {
+    let grid: Grid<char> = Grid::from_str("ab\ncd").unwrap();
+    assert_eq!("ab\ncd\n", grid.to_string());
+    assert_eq!("000\n000\n", Grid::new(3, 2, 0).to_string());
+}
+
+
+#[test]
+fn parse_chars() {
+    let grid: Grid<char> = Grid::from_str("ab\ncd\nef").unwrap();
+    assert_eq!(2, grid.width);
+    assert_eq!(3, grid.height);
+    assert_eq!('d', grid[Position{x: 1, y: 1}]);
+}
+
+
+#[test]
+fn parse_cells() {
+    let grid: Grid<Tile> = Grid::from_str("#.#\n..#").unwrap();
+    assert_eq!(3, grid.width);
+    assert_eq!(Tile::WALL, grid[Position{x: 2, y: 1}]);
+    assert_eq!(Tile::EMPTY, grid[Position{x: 0, y: 1}]);
+}
+
+
+#[test]
+fn parse_invalid_cell() {
+    let error = Grid::<Tile>::from_str("#.#\n.x#").unwrap_err();
+    assert_eq!(ParseGridError { line: 2, column: 2, reason: String::from("invalid cell 'x'") }, error);
+    assert_eq!("line 2, column 2: invalid cell 'x'", error.to_string());
+}
+
+
+#[test]
+fn parse_ragged_rows() {
+    let error = Grid::<char>::from_str("abc\nab\nabc").unwrap_err();
+    assert_eq!(ParseGridError { line: 2, column: 3, reason: String::from("row has width 2, expected 3") }, error);
+    let error = Grid::<char>::from_str("abc\nabcd").unwrap_err();
+    assert_eq!(4, error.column);
+}
+
+}
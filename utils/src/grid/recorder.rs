@@ -0,0 +1,209 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::imageops::{self, FilterType};
+use image::{Delay, DynamicImage, Frame, Rgb, RgbImage};
+
+use super::render::{Cell, Color, Render};
+use super::Grid;
+use crate::position::Position;
+
+
+/// Collects snapshots of a simulation, one pixel per cell, and writes them as an animated GIF or a PNG per frame.
+pub struct Recorder {
+    width: usize,
+    height: usize,
+    scale: u32,
+    delay: Duration,
+    background: Color,
+    frames: Vec<RgbImage>,
+}
+
+
+fn create_parent(path: &Path) -> Result<(), String> {
+    match path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        Some(dir) => fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e)),
+        None => Ok(()),
+    }
+}
+
+
+impl Recorder {
+
+    pub fn new(width: usize, height: usize) -> Recorder {
+        Recorder {
+            width,
+            height,
+            scale: 1,
+            delay: Duration::from_millis(100),
+            background: Color::BLACK,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Every cell becomes a `scale` x `scale` block of pixels in the written files.
+    pub fn scale(mut self, scale: u32) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// How long each frame of the GIF is shown.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// The color of cells without a point in `record_points`.
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    fn push(&mut self, frame: RgbImage) -> Result<(), String> {
+        if frame.dimensions() != (self.width as u32, self.height as u32) {
+            return Err(format!("frame of {}x{} does not fit the recording of {}x{}",
+                               frame.width(), frame.height(), self.width, self.height));
+        }
+        self.frames.push(frame);
+        Ok(())
+    }
+
+    /// Records the grid, the palette gives the color of each cell.
+    pub fn record<T, P>(&mut self, grid: &Grid<T>, palette: P) -> Result<(), String>
+        where P: Fn(&T) -> Color
+    {
+        self.record_render(&grid.render(|value| Cell::new(' ', palette(value))))
+    }
+
+    /// Records a render, with its overlays.
+    pub fn record_render<T, F>(&mut self, render: &Render<'_, T, F>) -> Result<(), String>
+        where F: Fn(&T) -> Cell
+    {
+        self.push(render.to_image(1))
+    }
+
+    /// Records the points in `color` on the background; points outside the recording are left out.
+    pub fn record_points<'a, I>(&mut self, points: I, color: Color) -> Result<(), String>
+        where I: IntoIterator<Item = &'a Position>
+    {
+        let Color { r, g, b } = self.background;
+        let mut frame = RgbImage::from_pixel(self.width as u32, self.height as u32, Rgb([r, g, b]));
+        let Color { r, g, b } = color;
+        for point in points.into_iter().filter(|p| p.x < self.width && p.y < self.height) {
+            frame.put_pixel(point.x as u32, point.y as u32, Rgb([r, g, b]));
+        }
+        self.push(frame)
+    }
+
+    fn scaled(&self, frame: &RgbImage) -> RgbImage {
+        imageops::resize(frame, frame.width() * self.scale, frame.height() * self.scale, FilterType::Nearest)
+    }
+
+    /// Writes all frames as an animated GIF that loops forever.
+    pub fn save_gif<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let path = path.as_ref();
+        let fail = |e: image::ImageError| format!("cannot write {}: {}", path.display(), e);
+        create_parent(path)?;
+        let file = File::create(path).map_err(|e| format!("cannot create {}: {}", path.display(), e))?;
+
+        let mut encoder = GifEncoder::new(file);
+        encoder.set_repeat(Repeat::Infinite).map_err(fail)?;
+        let delay = Delay::from_saturating_duration(self.delay);
+        encoder.encode_frames(self.frames.iter().map(|frame| {
+            let image = DynamicImage::ImageRgb8(self.scaled(frame)).to_rgba8();
+            Frame::from_parts(image, 0, 0, delay)
+        })).map_err(fail)
+    }
+
+    /// Writes every frame to `<dir>/<prefix>0000.png`, `<dir>/<prefix>0001.png`, ... and returns their paths.
+    pub fn save_png_sequence<P: AsRef<Path>>(&self, dir: P, prefix: &str) -> Result<Vec<PathBuf>, String> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+        let digits = self.frames.len().saturating_sub(1).to_string().len().max(4);
+        self.frames.iter()
+                   .enumerate()
+                   .map(|(nr, frame)| {
+                       let path = dir.join(format!("{}{:0digits$}.png", prefix, nr, digits = digits));
+                       self.scaled(frame)
+                           .save(&path)
+                           .map_err(|e| format!("cannot write {}: {}", path.display(), e))
+                           .map(|_| path)
+                   })
+                   .collect()
+    }
+
+}
+
+
+
+#[cfg(test)]
+pub mod recorder_tests {
+    use super::*;
+    use std::str::FromStr;
+    use image::AnimationDecoder;
+    use image::codecs::gif::GifDecoder;
+    use std::io::BufReader;
+
+const FOLDER: &str = "../target/recorder_tests";
+
+
+fn palette(c: &char) -> Color {
+    match c {
+        '#' => Color::GRAY,
+        _ => Color::BLACK,
+    }
+}
+
+
+#[test]
+fn record_frames() {
+    let mut grid: Grid<char> = Grid::from_str("#..\n...").unwrap();
+    let mut recorder = Recorder::new(3, 2).scale(2).background(Color::WHITE);
+    recorder.record(&grid, palette).unwrap();
+    grid.set(&Position { x: 2, y: 1 }, '#');
+    recorder.record_render(&grid.render(|c| Cell::new(*c, palette(c)))
+                                .overlay([Position { x: 1, y: 0 }], Cell::new('@', Color::RED))).unwrap();
+    recorder.record_points(&[Position { x: 0, y: 1 }, Position { x: 5, y: 5 }], Color::GREEN).unwrap();
+    assert_eq!(3, recorder.len());
+    assert!(recorder.record(&Grid::new(2, 2, '.'), palette).is_err());
+
+    let paths = recorder.save_png_sequence(format!("{}/png", FOLDER), "frame").unwrap();
+    assert_eq!(3, paths.len());
+    assert!(paths[2].ends_with("frame0002.png"));
+    let last = image::open(&paths[2]).unwrap().to_rgb8();
+    assert_eq!((6, 4), last.dimensions());
+    assert_eq!(&Rgb([31, 118, 5]), last.get_pixel(1, 3));
+    assert_eq!(&Rgb([255, 255, 255]), last.get_pixel(5, 3));
+    let second = image::open(&paths[1]).unwrap().to_rgb8();
+    assert_eq!(&Rgb([220, 50, 47]), second.get_pixel(3, 0));
+}
+
+
+#[test]
+fn animated_gif() {
+    let mut recorder = Recorder::new(4, 1).scale(3).delay(Duration::from_millis(50));
+    for x in 0..4 {
+        recorder.record_points(&[Position { x, y: 0 }], Color::WHITE).unwrap();
+    }
+    let path = format!("{}/moving.gif", FOLDER);
+    recorder.save_gif(&path).unwrap();
+
+    let decoder = GifDecoder::new(BufReader::new(File::open(&path).unwrap())).unwrap();
+    let frames = decoder.into_frames().collect_frames().unwrap();
+    assert_eq!(4, frames.len());
+    assert_eq!((12, 3), frames[0].buffer().dimensions());
+    assert_eq!(255, frames[2].buffer().get_pixel(7, 1)[0]);
+    assert_eq!(0, frames[2].buffer().get_pixel(1, 1)[0]);
+}
+
+}
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crossterm::{style, QueueableCommand};
+use image::{Rgb, RgbImage};
+
+use super::Grid;
+use crate::position::Position;
+
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const GRAY: Color = Color::rgb(128, 128, 128);
+    pub const RED: Color = Color::rgb(220, 50, 47);
+    pub const GREEN: Color = Color::rgb(31, 118, 5);
+    pub const BLUE: Color = Color::rgb(38, 139, 210);
+    pub const YELLOW: Color = Color::rgb(240, 200, 0);
+    pub const MAGENTA: Color = Color::rgb(211, 54, 130);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b }
+    }
+
+    /// One of a fixed set of colors that are easy to tell apart, repeating after ten.
+    pub fn indexed(index: usize) -> Color {
+        const SET: [Color; 10] = [
+            Color::RED, Color::GREEN, Color::BLUE, Color::YELLOW, Color::MAGENTA,
+            Color::rgb(42, 161, 152), Color::rgb(203, 75, 22), Color::rgb(108, 113, 196),
+            Color::rgb(133, 153, 0), Color::rgb(181, 137, 0),
+        ];
+        SET[index % SET.len()]
+    }
+
+    /// `count` colors with their hues spread evenly around the color wheel, all different.
+    pub fn palette(count: usize) -> Vec<Color> {
+        (0..count).map(|nr| Color::hue(nr as f64 * 360.0 / count as f64)).collect()
+    }
+
+    // a fairly saturated, not too bright color of the hue in degrees
+    fn hue(degrees: f64) -> Color {
+        let (saturation, value) = (0.75, 0.85);
+        let chroma = value * saturation;
+        let sector = degrees / 60.0;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let channel = |c: f64| ((c + value - chroma) * 255.0).round() as u8;
+        Color::rgb(channel(r), channel(g), channel(b))
+    }
+
+    /// Part of the way to the other color: 0 keeps this color, 1 gives the other.
+    pub fn blend(&self, other: &Color, amount: f64) -> Color {
+        let channel = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * amount.clamp(0.0, 1.0)).round() as u8;
+        Color::rgb(channel(self.r, other.r), channel(self.g, other.g), channel(self.b, other.b))
+    }
+}
+
+
+/// How one cell is drawn: the symbol in the terminal, the color in the terminal and in images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    pub color: Color,
+}
+
+
+impl Cell {
+    pub fn new(symbol: char, color: Color) -> Cell {
+        Cell { symbol, color }
+    }
+}
+
+
+/// A view of a grid where every cell is mapped to a `Cell` by `style`; overlays take precedence, the last one wins.
+pub struct Render<'a, T, F> {
+    grid: &'a Grid<T>,
+    style: F,
+    overlays: HashMap<Position, Cell>,
+}
+
+
+impl<T> Grid<T> {
+
+    pub fn render<F>(&self, style: F) -> Render<'_, T, F>
+        where F: Fn(&T) -> Cell
+    {
+        Render {
+            grid: self,
+            style,
+            overlays: HashMap::new(),
+        }
+    }
+
+}
+
+
+impl<T, F> Render<'_, T, F>
+    where F: Fn(&T) -> Cell
+{
+
+    /// Draws `cell` on each of the positions, e.g. to highlight a path.
+    pub fn overlay<I>(mut self, positions: I, cell: Cell) -> Self
+        where I: IntoIterator<Item = Position>
+    {
+        for position in positions {
+            self.overlays.insert(position, cell);
+        }
+        self
+    }
+
+    pub fn cell_at(&self, position: &Position) -> Cell {
+        match self.overlays.get(position) {
+            Some(cell) => *cell,
+            None => (self.style)(&self.grid[*position]),
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = Vec<Cell>> + '_ {
+        (0..self.grid.height).map(move |y| {
+            (0..self.grid.width).map(|x| self.cell_at(&Position { x, y }))
+                                .collect()
+        })
+    }
+
+    pub fn to_terminal<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        for row in self.rows() {
+            for cell in row {
+                let Color { r, g, b } = cell.color;
+                out.queue(style::SetForegroundColor(style::Color::Rgb { r, g, b }))?
+                   .queue(style::Print(cell.symbol))?;
+            }
+            out.queue(style::ResetColor)?
+               .queue(style::Print('\n'))?;
+        }
+        out.flush()
+    }
+
+    pub fn print(&self) {
+        self.to_terminal(&mut io::stdout()).unwrap();
+    }
+
+    /// Every cell becomes a `scale` x `scale` block of pixels in its color.
+    pub fn to_image(&self, scale: u32) -> RgbImage {
+        let scale = scale.max(1);
+        let width = self.grid.width as u32 * scale;
+        let height = self.grid.height as u32 * scale;
+        let cells: Vec<Cell> = self.rows().flatten().collect();
+
+        RgbImage::from_fn(width, height, |x, y| {
+            let position = Position { x: (x / scale) as usize, y: (y / scale) as usize };
+            let Color { r, g, b } = cells[position.to_index(self.grid)].color;
+            Rgb([r, g, b])
+        })
+    }
+
+    pub fn save_png<P: AsRef<Path>>(&self, path: P, scale: u32) -> Result<(), String> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+        }
+        self.to_image(scale)
+            .save(path)
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))
+    }
+
+}
+
+
+impl<T, F> Display for Render<'_, T, F>
+    where F: Fn(&T) -> Cell
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", row.iter().map(|cell| cell.symbol).collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+
+
+#[cfg(test)]
+pub mod render_tests {
+    use super::*;
+    use std::str::FromStr;
+
+fn style(c: &char) -> Cell {
+    match c {
+        '#' => Cell::new('█', Color::GRAY),
+        _ => Cell::new(' ', Color::BLACK),
+    }
+}
+
+
+#[test]
+fn display_with_overlay() {
+    let grid: Grid<char> = Grid::from_str("###\n#.#\n#..").unwrap();
+    let render = grid.render(style)
+                     .overlay([Position{x: 1, y: 1}, Position{x: 1, y: 2}], Cell::new('o', Color::RED))
+                     .overlay([Position{x: 2, y: 2}], Cell::new('x', Color::GREEN));
+    assert_eq!("███\n█o█\n█ox\n", render.to_string());
+    assert_eq!(Cell::new('o', Color::RED), render.cell_at(&Position{x: 1, y: 2}));
+}
+
+
+#[test]
+fn terminal() {
+    let grid: Grid<char> = Grid::from_str("#.").unwrap();
+    let mut out = Vec::new();
+    grid.render(style).to_terminal(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!("\x1b[38;2;128;128;128m█\x1b[38;2;0;0;0m \x1b[0m\n", out);
+}
+
+
+#[test]
+fn image() {
+    let grid: Grid<char> = Grid::from_str("#.\n..").unwrap();
+    let image = grid.render(style)
+                    .overlay([Position{x: 1, y: 1}], Cell::new('o', Color::RED))
+                    .to_image(2);
+    assert_eq!((4, 4), image.dimensions());
+    assert_eq!(&Rgb([128, 128, 128]), image.get_pixel(1, 1));
+    assert_eq!(&Rgb([0, 0, 0]), image.get_pixel(2, 1));
+    assert_eq!(&Rgb([220, 50, 47]), image.get_pixel(3, 3));
+}
+
+
+#[test]
+fn palette() {
+    let colors = Color::palette(26);
+    assert_eq!(26, colors.iter().collect::<std::collections::HashSet<_>>().len());
+    assert_eq!(Color::rgb(217, 54, 54), colors[0]);
+    assert!(Color::palette(0).is_empty());
+    assert_eq!(Color::rgb(128, 128, 128), Color::BLACK.blend(&Color::WHITE, 0.5));
+    assert_eq!(Color::RED, Color::RED.blend(&Color::WHITE, 0.0));
+}
+
+}
@@ -0,0 +1,187 @@
+use std::fmt::{Display, Write};
+use std::fs;
+use std::path::Path;
+
+use super::render::Color;
+use super::Grid;
+use crate::point::Point;
+use crate::position::Position;
+use crate::regions::Region;
+
+
+/// An SVG picture of a grid: cells as squares, with region fences, paths and labels drawn on top in the order they are added.
+/// Coordinates are in cells, the top left corner of cell (x, y) is at (x, y).
+pub struct Svg {
+    width: usize,
+    height: usize,
+    scale: u32,
+    elements: Vec<String>,
+}
+
+
+fn hex(color: &Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+
+fn center(position: &Position) -> (f64, f64) {
+    (position.x as f64 + 0.5, position.y as f64 + 0.5)
+}
+
+
+// one closed subpath through the corners
+fn subpath(corners: &[Point]) -> String {
+    let mut d = String::new();
+    for (nr, corner) in corners.iter().enumerate() {
+        write!(d, "{}{} {} ", if nr == 0 { "M" } else { "L" }, corner.x, corner.y).unwrap();
+    }
+    d.push('Z');
+    d
+}
+
+
+impl<T> Grid<T> {
+
+    /// An SVG picture with every cell filled in the color given by `fill`.
+    pub fn svg<F>(&self, fill: F) -> Svg
+        where F: Fn(&T) -> Color
+    {
+        let mut svg = Svg::new(self.width, self.height);
+        for position in self.iter_lr_down() {
+            svg.elements.push(format!("<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{}\"/>",
+                                      position.x, position.y, hex(&fill(&self[position]))));
+        }
+        svg
+    }
+
+}
+
+
+impl Svg {
+
+    /// An empty picture of width x height cells.
+    pub fn new(width: usize, height: usize) -> Svg {
+        Svg { width, height, scale: 16, elements: Vec::new() }
+    }
+
+    /// The size of a cell in pixels when the picture is shown.
+    pub fn scale(mut self, scale: u32) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// The fence around the region, holes included.
+    pub fn region(mut self, region: &Region, color: Color) -> Self {
+        let mut d = subpath(&region.outline());
+        for hole in region.holes() {
+            d.push(' ');
+            d.push_str(&subpath(&hole.outline()));
+        }
+        self.elements.push(format!("<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.1\"/>", d, hex(&color)));
+        self
+    }
+
+    /// A line through the centers of the cells, with a dot where it turns.
+    pub fn path(mut self, positions: &[Position], color: Color) -> Self {
+        let mut positions = positions.to_vec();
+        positions.dedup();
+        let points: Vec<String> = positions.iter()
+                                           .map(center)
+                                           .map(|(x, y)| format!("{},{}", x, y))
+                                           .collect();
+        self.elements.push(format!("<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.15\"/>",
+                                   points.join(" "), hex(&color)));
+
+        for turn in positions.windows(3).filter(|w| w[1].distance_to(&w[0]) != w[2].distance_to(&w[1])) {
+            let (x, y) = center(&turn[1]);
+            self.elements.push(format!("<circle cx=\"{}\" cy=\"{}\" r=\"0.2\" fill=\"{}\"/>", x, y, hex(&color)));
+        }
+        self
+    }
+
+    /// Text centered on the cell.
+    pub fn label(mut self, position: &Position, text: &str) -> Self {
+        let (x, y) = center(position);
+        self.elements.push(format!("<text x=\"{}\" y=\"{}\" font-size=\"0.4\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>",
+                                   x, y, escape(text)));
+        self
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+        }
+        fs::write(path, self.to_string()).map_err(|e| format!("cannot write {}: {}", path.display(), e))
+    }
+
+}
+
+
+impl Display for Svg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scale = self.scale as usize;
+        writeln!(f, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+                 self.width * scale, self.height * scale, self.width, self.height)?;
+        for element in self.elements.iter() {
+            writeln!(f, "  {}", element)?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+
+
+#[cfg(test)]
+pub mod svg_tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::regions::{regions, Connectivity};
+
+
+#[test]
+fn snapshot() {
+    let grid: Grid<char> = Grid::from_str("AAB\nABB").unwrap();
+    let mut svg = grid.svg(|c| Color::indexed(*c as usize - 'A' as usize)).scale(10);
+    for region in regions(&grid, Connectivity::FOUR) {
+        let text = format!("{} {}/{}", grid[region.start()], region.area(), region.perimeter());
+        svg = svg.region(&region, Color::BLACK).label(&region.start(), &text);
+    }
+    let svg = svg.path(&[Position { x: 0, y: 1 }, Position { x: 0, y: 0 }, Position { x: 0, y: 0 },
+                         Position { x: 1, y: 0 }, Position { x: 2, y: 0 }], Color::RED)
+                 .label(&Position { x: 2, y: 1 }, "<&>");
+
+    assert_eq!(
+r##"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="20" viewBox="0 0 3 2">
+  <rect x="0" y="0" width="1" height="1" fill="#dc322f"/>
+  <rect x="1" y="0" width="1" height="1" fill="#dc322f"/>
+  <rect x="2" y="0" width="1" height="1" fill="#1f7605"/>
+  <rect x="0" y="1" width="1" height="1" fill="#dc322f"/>
+  <rect x="1" y="1" width="1" height="1" fill="#1f7605"/>
+  <rect x="2" y="1" width="1" height="1" fill="#1f7605"/>
+  <path d="M0 0 L2 0 L2 1 L1 1 L1 2 L0 2 Z" fill="none" stroke="#000000" stroke-width="0.1"/>
+  <text x="0.5" y="0.5" font-size="0.4" text-anchor="middle" dominant-baseline="central">A 3/8</text>
+  <path d="M2 0 L3 0 L3 2 L1 2 L1 1 L2 1 Z" fill="none" stroke="#000000" stroke-width="0.1"/>
+  <text x="2.5" y="0.5" font-size="0.4" text-anchor="middle" dominant-baseline="central">B 3/8</text>
+  <polyline points="0.5,1.5 0.5,0.5 1.5,0.5 2.5,0.5" fill="none" stroke="#dc322f" stroke-width="0.15"/>
+  <circle cx="0.5" cy="0.5" r="0.2" fill="#dc322f"/>
+  <text x="2.5" y="1.5" font-size="0.4" text-anchor="middle" dominant-baseline="central">&lt;&amp;&gt;</text>
+</svg>
+"##, svg.to_string());
+}
+
+
+#[test]
+fn fence_with_hole() {
+    let grid: Grid<char> = Grid::from_str("OOO\nOXO\nOOO").unwrap();
+    let ring = &regions(&grid, Connectivity::FOUR)[0];
+    let svg = Svg::new(3, 3).region(ring, Color::BLUE).to_string();
+    assert!(svg.contains("d=\"M0 0 L3 0 L3 3 L0 3 Z M1 1 L2 1 L2 2 L1 2 Z\""));
+}
+
+}
@@ -0,0 +1,214 @@
+use super::Grid;
+use crate::point::{Point, Vector};
+use crate::position::Position;
+use crate::Move;
+
+
+/// The topology of a grid whose edges wrap around: leaving it on one side enters it on the opposite side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Torus {
+    pub width: usize,
+    pub height: usize,
+}
+
+
+impl<T> Grid<T> {
+
+    pub fn torus(&self) -> Torus {
+        Torus::new(self.width, self.height)
+    }
+
+    /// The cell at a point, where the grid repeats itself endlessly in every direction.
+    pub fn at_wrapping(&self, point: &Point) -> &T {
+        &self[self.torus().wrap(point)]
+    }
+
+}
+
+
+// the length of the blocks along one axis and the gap between them: the blocks fill the size exactly,
+// or, when that does not come out even, they are separated by a single line like the middle of an odd width
+fn blocks(size: usize, parts: usize) -> Result<(usize, usize), String> {
+    if parts == 0 {
+        return Err(String::from("a partition needs at least one block"));
+    }
+    let (length, gap) = if size.is_multiple_of(parts) {
+        (size / parts, 0)
+    } else {
+        match size.checked_sub(parts - 1) {
+            Some(rest) if rest.is_multiple_of(parts) => (rest / parts, 1),
+            _ => (0, 0),
+        }
+    };
+    if length == 0 {
+        return Err(format!("{} lines do not split into {} equal blocks, with or without a line between them", size, parts));
+    }
+    Ok((length, gap))
+}
+
+
+fn block_of(position: &Position, horizontal: (usize, usize), vertical: (usize, usize), columns: usize) -> Option<usize> {
+    let column = block(position.x, horizontal)?;
+    let row = block(position.y, vertical)?;
+    Some(row * columns + column)
+}
+
+
+fn block(coordinate: usize, (length, gap): (usize, usize)) -> Option<usize> {
+    let stride = length + gap;
+    (coordinate % stride < length).then_some(coordinate / stride)
+}
+
+
+impl Torus {
+
+    pub fn new(width: usize, height: usize) -> Torus {
+        assert!(width > 0 && height > 0, "a torus of {}x{} has no cells", width, height);
+        Torus { width, height }
+    }
+
+    pub fn wrap(&self, point: &Point) -> Position {
+        Position {
+            x: point.x.rem_euclid(self.width as i64) as usize,
+            y: point.y.rem_euclid(self.height as i64) as usize,
+        }
+    }
+
+    /// Where `steps` moves by `velocity` end; negative steps go back in time.
+    pub fn jump(&self, position: &Position, velocity: &Vector, steps: i64) -> Position {
+        // in 128 bits the product cannot overflow
+        let along = |start: usize, speed: i64, size: usize| {
+            (start as i128 + speed as i128 * steps as i128).rem_euclid(size as i128) as usize
+        };
+        Position {
+            x: along(position.x, velocity.x, self.width),
+            y: along(position.y, velocity.y, self.height),
+        }
+    }
+
+    pub fn move_by(&self, position: &Position, velocity: &Vector) -> Position {
+        self.jump(position, velocity, 1)
+    }
+
+    pub fn neighbour(&self, position: &Position, direction: &Move) -> Position {
+        self.move_by(position, &Vector::from(direction))
+    }
+
+    /// The direct sides, in the order of `Move::direct_sides`.
+    pub fn sides(&self, position: &Position) -> [Position; 4] {
+        Move::direct_sides().map(|direction| self.neighbour(position, &direction))
+    }
+
+    pub fn around(&self, position: &Position) -> [Position; 8] {
+        Move::all_sides().map(|direction| self.neighbour(position, &direction))
+    }
+
+    /// Which of `columns` x `rows` equal blocks a position is in, numbered row by row.
+    /// If the size leaves a line between the blocks, like the middle of an odd width, cells on it are in none.
+    /// Fails when the width or height does not split into equal blocks either way.
+    pub fn partition(&self, position: &Position, columns: usize, rows: usize) -> Result<Option<usize>, String> {
+        let (horizontal, vertical) = (blocks(self.width, columns)?, blocks(self.height, rows)?);
+        Ok(block_of(position, horizontal, vertical, columns))
+    }
+
+    /// 0 top left, 1 top right, 2 bottom left, 3 bottom right; the middle lines are in none.
+    /// A torus one cell wide or high has no quadrants.
+    pub fn quadrant(&self, position: &Position) -> Option<usize> {
+        self.partition(position, 2, 2).ok().flatten()
+    }
+
+    /// How many of the positions are in each block of the partition.
+    pub fn count_per_partition<I>(&self, positions: I, columns: usize, rows: usize) -> Result<Vec<usize>, String>
+        where I: IntoIterator<Item = Position>
+    {
+        let (horizontal, vertical) = (blocks(self.width, columns)?, blocks(self.height, rows)?);
+        let mut counts = vec![0; columns * rows];
+        for position in positions {
+            if let Some(index) = block_of(&position, horizontal, vertical, columns) {
+                counts[index] += 1;
+            }
+        }
+        Ok(counts)
+    }
+
+}
+
+
+
+#[cfg(test)]
+pub mod torus_tests {
+    use super::*;
+    use std::str::FromStr;
+
+
+#[test]
+fn wrapping_moves() {
+    let torus = Torus::new(11, 7);
+    let start = Position { x: 2, y: 4 };
+    let velocity = Vector::new(2, -3);
+    assert_eq!(Position { x: 4, y: 1 }, torus.move_by(&start, &velocity));
+    assert_eq!(Position { x: 6, y: 5 }, torus.jump(&start, &velocity, 2));
+    assert_eq!(Position { x: 10, y: 6 }, torus.jump(&start, &velocity, 4));
+    assert_eq!(start, torus.jump(&start, &velocity, 77));
+    assert_eq!(start, torus.jump(&torus.jump(&start, &velocity, 5), &velocity, -5));
+    assert_eq!(torus.jump(&start, &velocity, 3), torus.jump(&start, &velocity, 3 + 77 * 1_000_000_000_000));
+    assert_eq!(torus.wrap(&Point::new(-9, 11)), Position { x: 2, y: 4 });
+}
+
+
+#[test]
+fn wrapping_neighbours() {
+    let grid = Grid::from_str("abc\ndef").unwrap();
+    let torus = grid.torus();
+    let corner = Position { x: 0, y: 0 };
+    assert_eq!([Position { x: 0, y: 1 }, Position { x: 1, y: 0 }, Position { x: 0, y: 1 }, Position { x: 2, y: 0 }],
+               torus.sides(&corner));
+    assert_eq!(Position { x: 2, y: 1 }, torus.neighbour(&corner, &Move::TOP_LEFT));
+    assert_eq!(8, torus.around(&corner).len());
+    assert_eq!('f', *grid.at_wrapping(&Point::new(-1, -1)));
+    assert_eq!('b', *grid.at_wrapping(&Point::new(7, 4)));
+}
+
+
+#[test]
+fn partitions() {
+    let torus = Torus::new(11, 7);
+    assert_eq!(Some(0), torus.quadrant(&Position { x: 4, y: 2 }));
+    assert_eq!(None, torus.quadrant(&Position { x: 5, y: 2 }));
+    assert_eq!(Some(1), torus.quadrant(&Position { x: 6, y: 0 }));
+    assert_eq!(None, torus.quadrant(&Position { x: 6, y: 3 }));
+    assert_eq!(Some(3), torus.quadrant(&Position { x: 10, y: 6 }));
+
+    let even = Torus::new(6, 4);
+    assert_eq!(Ok(Some(1)), even.partition(&Position { x: 2, y: 0 }, 3, 1));
+    assert_eq!(Ok(Some(5)), even.partition(&Position { x: 5, y: 3 }, 3, 2));
+    assert_eq!(Ok(vec![1, 0, 2, 0]), even.count_per_partition([Position { x: 0, y: 0 }, Position { x: 1, y: 3 }, Position { x: 2, y: 2 }], 2, 2));
+    assert!(even.partition(&Position { x: 0, y: 0 }, 0, 1).is_err());
+    assert!(even.count_per_partition([], 2, 0).is_err());
+    assert_eq!(None, Torus::new(1, 5).quadrant(&Position { x: 0, y: 0 }));
+}
+
+
+#[test]
+fn partitions_in_three() {
+    let blocks_of = |torus: Torus| (0..torus.width).map(|x| torus.partition(&Position { x, y: 0 }, 3, 1)).collect::<Result<Vec<_>, _>>();
+    // 9 splits evenly, 11 with a line between the blocks, 10 not at all
+    assert_eq!(Ok(vec![Some(0), Some(0), Some(0), Some(1), Some(1), Some(1), Some(2), Some(2), Some(2)]), blocks_of(Torus::new(9, 1)));
+    assert_eq!(Ok(vec![Some(0), Some(0), Some(0), None, Some(1), Some(1), Some(1), None, Some(2), Some(2), Some(2)]), blocks_of(Torus::new(11, 1)));
+    assert!(blocks_of(Torus::new(10, 1)).is_err());
+    assert!(blocks_of(Torus::new(2, 1)).is_err());
+}
+
+
+#[test]
+fn partitions_in_four() {
+    let everywhere = |torus: Torus| torus.count_per_partition((0..torus.width).flat_map(|x| (0..torus.height).map(move |y| Position { x, y })), 4, 4);
+    assert_eq!(Ok(vec![4; 16]), everywhere(Torus::new(8, 8)));
+    // 11 leaves a line after every block of 2, 12 needs none
+    assert_eq!(Ok(vec![6; 16]), everywhere(Torus::new(11, 12)));
+    assert_eq!(Ok(Some(15)), Torus::new(11, 12).partition(&Position { x: 10, y: 11 }, 4, 4));
+    assert_eq!(Ok(None), Torus::new(11, 12).partition(&Position { x: 8, y: 0 }, 4, 4));
+    assert!(everywhere(Torus::new(10, 8)).is_err());
+}
+
+}
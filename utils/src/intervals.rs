@@ -0,0 +1,270 @@
+use std::fmt::Display;
+use std::ops::{Range, RangeInclusive};
+
+
+/// A set of whole numbers, kept as sorted half-open ranges that neither overlap nor touch.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    ranges: Vec<Range<i64>>,
+}
+
+
+// an inclusive range as half-open; the end saturates, so i64::MAX itself cannot be in a set
+fn half_open(range: &RangeInclusive<i64>) -> Range<i64> {
+    *range.start()..range.end().saturating_add(1)
+}
+
+
+impl IntervalSet {
+
+    pub fn new() -> IntervalSet {
+        IntervalSet { ranges: Vec::new() }
+    }
+
+    /// Sorts the ranges, drops the empty ones and merges those that overlap or touch.
+    pub fn from_ranges<I>(ranges: I) -> IntervalSet
+        where I: IntoIterator<Item = Range<i64>>
+    {
+        let mut sorted: Vec<Range<i64>> = ranges.into_iter().filter(|r| !r.is_empty()).collect();
+        sorted.sort_by_key(|r| r.start);
+
+        let mut merged: Vec<Range<i64>> = Vec::with_capacity(sorted.len());
+        for range in sorted {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        IntervalSet { ranges: merged }
+    }
+
+    pub fn from_inclusive<I>(ranges: I) -> IntervalSet
+        where I: IntoIterator<Item = RangeInclusive<i64>>
+    {
+        IntervalSet::from_ranges(ranges.into_iter().map(|r| half_open(&r)))
+    }
+
+    /// The normalised ranges, in ascending order.
+    pub fn ranges(&self) -> &[Range<i64>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many numbers are in the set.
+    pub fn covered(&self) -> u64 {
+        self.ranges.iter().map(|r| r.start.abs_diff(r.end)).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.ranges.last().map(|r| r.end - 1)
+    }
+
+    pub fn insert(&mut self, range: Range<i64>) {
+        *self = self.union(&IntervalSet::from(range));
+    }
+
+    pub fn insert_inclusive(&mut self, range: RangeInclusive<i64>) {
+        self.insert(half_open(&range));
+    }
+
+    // the range that could hold the value: the last one starting at or before it
+    fn candidate(&self, value: i64) -> Option<&Range<i64>> {
+        let idx = self.ranges.partition_point(|r| r.start <= value);
+        idx.checked_sub(1).map(|idx| &self.ranges[idx])
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.candidate(value).is_some_and(|r| value < r.end)
+    }
+
+    /// True when every number of the range is in the set; an empty range always is.
+    pub fn contains_range(&self, range: &Range<i64>) -> bool {
+        range.is_empty() || self.candidate(range.start).is_some_and(|r| range.end <= r.end)
+    }
+
+    pub fn contains_inclusive(&self, range: &RangeInclusive<i64>) -> bool {
+        self.contains_range(&half_open(range))
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet::from_ranges(self.ranges.iter().chain(other.ranges.iter()).cloned())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if a.end < b.end { i += 1 } else { j += 1 }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// The numbers of this set that are not in the other.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+        for range in self.ranges.iter() {
+            let mut start = range.start;
+            // skip what ends before this range, those cannot cut anything later either
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < range.end {
+                if other.ranges[k].start > start {
+                    ranges.push(start..other.ranges[k].start);
+                }
+                start = start.max(other.ranges[k].end);
+                k += 1;
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    pub fn shift(&self, offset: i64) -> IntervalSet {
+        IntervalSet { ranges: self.ranges.iter().map(|r| r.start + offset..r.end + offset).collect() }
+    }
+
+    /// Moves the numbers in each source range by its offset; numbers in no source range stay where they are.
+    /// When source ranges overlap, the first one that holds a number decides its offset.
+    pub fn map_offsets(&self, pieces: &[(Range<i64>, i64)]) -> IntervalSet {
+        let mut unmapped = self.clone();
+        let mut mapped = Vec::new();
+        for (source, offset) in pieces {
+            let source = IntervalSet::from(source.clone());
+            mapped.extend(unmapped.intersection(&source).shift(*offset).ranges);
+            unmapped = unmapped.difference(&source);
+        }
+        IntervalSet::from_ranges(mapped.into_iter().chain(unmapped.ranges))
+    }
+
+}
+
+
+impl From<Range<i64>> for IntervalSet {
+    fn from(range: Range<i64>) -> Self {
+        IntervalSet::from_ranges(std::iter::once(range))
+    }
+}
+
+
+impl From<RangeInclusive<i64>> for IntervalSet {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        IntervalSet::from(half_open(&range))
+    }
+}
+
+
+impl FromIterator<Range<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+        IntervalSet::from_ranges(iter)
+    }
+}
+
+
+impl FromIterator<RangeInclusive<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<i64>>>(iter: I) -> Self {
+        IntervalSet::from_inclusive(iter)
+    }
+}
+
+
+impl Display for IntervalSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ranges: Vec<String> = self.ranges.iter().map(|r| format!("{}..{}", r.start, r.end)).collect();
+        write!(f, "{{{}}}", ranges.join(", "))
+    }
+}
+
+
+
+#[cfg(test)]
+pub mod intervals_tests {
+    use super::*;
+
+
+#[test]
+fn normalise() {
+    let set = IntervalSet::from_ranges([10..14, 3..5, 5..6, 12..20, 8..8, Range { start: 30, end: 25 }]);
+    assert_eq!(&[3..6, 10..20], set.ranges());
+    assert_eq!(13, set.covered());
+    assert_eq!((Some(3), Some(19)), (set.min(), set.max()));
+    assert_eq!("{3..6, 10..20}", set.to_string());
+
+    // inclusive ranges that touch are merged as well
+    let inclusive: IntervalSet = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+    assert_eq!(&[3..6, 10..21], inclusive.ranges());
+    assert_eq!(14, inclusive.covered());
+    assert!(IntervalSet::new().is_empty());
+    assert_eq!(0, IntervalSet::new().covered());
+}
+
+
+#[test]
+fn membership() {
+    let set = IntervalSet::from_inclusive([3..=5, 10..=14, 16..=20, 12..=18]);
+    let fresh: Vec<i64> = [1, 5, 8, 11, 17, 32].into_iter().filter(|id| set.contains(*id)).collect();
+    assert_eq!(vec![5, 11, 17], fresh);
+    assert!(!set.contains(6) && !set.contains(2) && !set.contains(21));
+    assert!(set.contains_range(&(11..21)));
+    assert!(!set.contains_range(&(5..11)));
+    assert!(set.contains_range(&(40..40)));
+    assert!(set.contains_inclusive(&(10..=20)));
+    assert!(!set.contains_inclusive(&(10..=21)));
+
+    let mut set = set;
+    set.insert(6..10);
+    set.insert_inclusive(21..=21);
+    assert_eq!(IntervalSet::from(3..=21), set);
+}
+
+
+#[test]
+fn set_operations() {
+    let a = IntervalSet::from_ranges([0..10, 20..30, 40..50]);
+    let b = IntervalSet::from_ranges([5..25, 28..42, 60..70]);
+    assert_eq!(&[0..50, 60..70], a.union(&b).ranges());
+    assert_eq!(&[5..10, 20..25, 28..30, 40..42], a.intersection(&b).ranges());
+    assert_eq!(&[0..5, 25..28, 42..50], a.difference(&b).ranges());
+    assert_eq!(&[10..20, 30..40, 60..70], b.difference(&a).ranges());
+    assert_eq!(a, a.difference(&IntervalSet::new()));
+    assert!(a.difference(&a).is_empty());
+    assert!(a.intersection(&IntervalSet::new()).is_empty());
+
+    // covered length of a union adds up with the intersection
+    assert_eq!(a.covered() + b.covered(), a.union(&b).covered() + a.intersection(&b).covered());
+}
+
+
+#[test]
+fn offset_mapping() {
+    // seed-to-soil map of 2023 day 5: 50 98 2 and 52 50 48
+    let pieces = [(98..100, -48), (50..98, 2)];
+    let seeds = IntervalSet::from_ranges([79..93, 55..68]);
+    assert_eq!(&[57..70, 81..95], seeds.map_offsets(&pieces).ranges());
+    // a range over the edge of both pieces is split, the rest is kept as it is
+    let wide = IntervalSet::from(45..99);
+    assert_eq!(&[45..51, 52..100], wide.map_offsets(&pieces).ranges());
+    assert_eq!(wide.covered(), wide.map_offsets(&pieces).covered());
+
+    // the first piece that holds a number wins
+    let overlapping = [(0..10, 100), (5..15, 200)];
+    assert_eq!(&[100..110, 210..215], IntervalSet::from(0..15).map_offsets(&overlapping).ranges());
+}
+
+}
@@ -1,11 +1,22 @@
 use std::ops::{Index, IndexMut};
 
 
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod dag;
 pub mod grid;
+pub mod intervals;
+pub mod point;
 pub mod position;
+pub mod regions;
+pub mod rewrite;
+pub mod search;
+pub mod solution;
 
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
 pub enum Move {
     TOP,
     TOP_RIGHT,
@@ -36,6 +47,11 @@ impl Move {
     }
 
 
+    pub fn all_sides() -> [Move;8] {
+        [Move::TOP, Move::TOP_RIGHT, Move::RIGHT, Move::DOWN_RIGHT, Move::DOWN, Move::DOWN_LEFT, Move::LEFT, Move::TOP_LEFT]
+    }
+
+
     pub fn move_90(&self) -> Move {
         match self {
             Move::TOP => Move::RIGHT,
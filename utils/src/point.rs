@@ -0,0 +1,333 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::grid::Grid;
+use crate::position::Position;
+use crate::Move;
+
+
+/// A signed location, unlike `Position` it may lie outside any grid.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+
+/// The difference between two points; y grows downwards, as in a grid.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+
+impl Point {
+
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn checked_add(&self, vector: &Vector) -> Option<Point> {
+        Some(Point { x: self.x.checked_add(vector.x)?, y: self.y.checked_add(vector.y)? })
+    }
+
+    pub fn checked_sub(&self, vector: &Vector) -> Option<Point> {
+        Some(Point { x: self.x.checked_sub(vector.x)?, y: self.y.checked_sub(vector.y)? })
+    }
+
+    /// The vector from the other point to this one, or None if it does not fit.
+    pub fn checked_vector_from(&self, other: &Point) -> Option<Vector> {
+        Some(Vector { x: self.x.checked_sub(other.x)?, y: self.y.checked_sub(other.y)? })
+    }
+
+    pub fn manhattan_distance(&self, other: &Point) -> u64 {
+        (*other - *self).manhattan()
+    }
+
+    pub fn chebyshev_distance(&self, other: &Point) -> u64 {
+        (*other - *self).chebyshev()
+    }
+
+    pub fn checked_manhattan_distance(&self, other: &Point) -> Option<u64> {
+        other.checked_vector_from(self)?.checked_manhattan()
+    }
+
+    pub fn checked_chebyshev_distance(&self, other: &Point) -> Option<u64> {
+        other.checked_vector_from(self)?.checked_chebyshev()
+    }
+
+    /// The position in the grid, or None if the point lies outside it.
+    pub fn to_position<T>(&self, grid: &Grid<T>) -> Option<Position> {
+        Position::try_from(*self).ok()
+                                 .filter(|position| grid.in_grid(position))
+    }
+
+    /// The point of a position in the grid, or None if the position lies outside it.
+    pub fn from_position<T>(position: &Position, grid: &Grid<T>) -> Option<Point> {
+        Point::try_from(*position).ok()
+                                  .filter(|_| grid.in_grid(position))
+    }
+
+}
+
+
+impl Vector {
+
+    pub const fn new(x: i64, y: i64) -> Vector {
+        Vector { x, y }
+    }
+
+    pub fn checked_add(&self, other: &Vector) -> Option<Vector> {
+        Some(Vector { x: self.x.checked_add(other.x)?, y: self.y.checked_add(other.y)? })
+    }
+
+    pub fn checked_sub(&self, other: &Vector) -> Option<Vector> {
+        Some(Vector { x: self.x.checked_sub(other.x)?, y: self.y.checked_sub(other.y)? })
+    }
+
+    pub fn checked_mul(&self, factor: i64) -> Option<Vector> {
+        Some(Vector { x: self.x.checked_mul(factor)?, y: self.y.checked_mul(factor)? })
+    }
+
+    pub fn checked_neg(&self) -> Option<Vector> {
+        Some(Vector { x: self.x.checked_neg()?, y: self.y.checked_neg()? })
+    }
+
+    pub fn manhattan(&self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    pub fn chebyshev(&self) -> u64 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+
+    /// The manhattan norm, or None if it does not fit; two coordinates of i64::MIN add up to 2^64.
+    pub fn checked_manhattan(&self) -> Option<u64> {
+        self.x.unsigned_abs().checked_add(self.y.unsigned_abs())
+    }
+
+    /// The chebyshev norm always fits, the unsigned absolute value of i64::MIN is 2^63.
+    pub fn checked_chebyshev(&self) -> Option<u64> {
+        Some(self.chebyshev())
+    }
+
+    /// Turned 90 degrees clockwise on screen, like `Move::move_90`.
+    pub fn rotate_right(&self) -> Vector {
+        Vector { x: -self.y, y: self.x }
+    }
+
+    pub fn rotate_left(&self) -> Vector {
+        Vector { x: self.y, y: -self.x }
+    }
+
+}
+
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        Point { x: self.x + rhs.x, y: self.y + rhs.y }
+    }
+}
+
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vector) -> Self::Output {
+        Point { x: self.x - rhs.x, y: self.y - rhs.y }
+    }
+}
+
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Vector { x: self.x - rhs.x, y: self.y - rhs.y }
+    }
+}
+
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        Vector { x: self.x + rhs.x, y: self.y + rhs.y }
+    }
+}
+
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Self::Output {
+        Vector { x: self.x - rhs.x, y: self.y - rhs.y }
+    }
+}
+
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Vector { x: self.x * rhs, y: self.y * rhs }
+    }
+}
+
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Self::Output {
+        Vector { x: -self.x, y: -self.y }
+    }
+}
+
+
+impl From<&Move> for Vector {
+    fn from(direction: &Move) -> Self {
+        match direction {
+            Move::TOP => Vector::new(0, -1),
+            Move::TOP_RIGHT => Vector::new(1, -1),
+            Move::RIGHT => Vector::new(1, 0),
+            Move::DOWN_RIGHT => Vector::new(1, 1),
+            Move::DOWN => Vector::new(0, 1),
+            Move::DOWN_LEFT => Vector::new(-1, 1),
+            Move::LEFT => Vector::new(-1, 0),
+            Move::TOP_LEFT => Vector::new(-1, -1),
+        }
+    }
+}
+
+
+impl TryFrom<Position> for Point {
+    type Error = String;
+
+    fn try_from(position: Position) -> Result<Self, Self::Error> {
+        match (i64::try_from(position.x), i64::try_from(position.y)) {
+            (Ok(x), Ok(y)) => Ok(Point { x, y }),
+            _ => Err(format!("{} does not fit in a point", position)),
+        }
+    }
+}
+
+
+impl TryFrom<Point> for Position {
+    type Error = String;
+
+    fn try_from(point: Point) -> Result<Self, Self::Error> {
+        match (usize::try_from(point.x), usize::try_from(point.y)) {
+            (Ok(x), Ok(y)) => Ok(Position { x, y }),
+            _ => Err(format!("{} is not a position", point)),
+        }
+    }
+}
+
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{},{}]", self.x, self.y)
+    }
+}
+
+
+impl Display for Vector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{},{}>", self.x, self.y)
+    }
+}
+
+
+
+#[cfg(test)]
+pub mod point_tests {
+    use super::*;
+
+
+#[test]
+fn arithmetic() {
+    let point = Point::new(2, 3);
+    let vector = Vector::new(-3, 1);
+    assert_eq!(Point::new(-1, 4), point + vector);
+    assert_eq!(Point::new(5, 2), point - vector);
+    assert_eq!(vector, (point + vector) - point);
+    assert_eq!(Vector::new(-9, 3), vector * 3);
+    assert_eq!(Vector::new(3, -1), -vector);
+    assert_eq!(Vector::new(-1, 2), vector + Vector::new(2, 1));
+
+    let mut moved = point;
+    moved += vector;
+    moved -= vector * 2;
+    assert_eq!(Point::new(5, 2), moved);
+
+    assert_eq!(None, Point::new(i64::MAX, 0).checked_add(&Vector::new(1, 0)));
+    assert_eq!(None, Vector::new(i64::MAX, 1).checked_mul(2));
+
+    assert_eq!(Some(point - vector), point.checked_sub(&vector));
+    assert_eq!(None, Point::new(i64::MIN, 0).checked_sub(&Vector::new(1, 0)));
+    assert_eq!(Some(vector), (point + vector).checked_vector_from(&point));
+    assert_eq!(None, Point::new(i64::MAX, 0).checked_vector_from(&Point::new(-1, 0)));
+    assert_eq!(Some(Vector::new(-1, 2)), vector.checked_add(&Vector::new(2, 1)));
+    assert_eq!(None, Vector::new(0, i64::MAX).checked_add(&Vector::new(0, 1)));
+    assert_eq!(Some(Vector::new(-5, 0)), vector.checked_sub(&Vector::new(2, 1)));
+    assert_eq!(None, Vector::new(i64::MIN, 0).checked_sub(&Vector::new(1, 0)));
+    assert_eq!(Some(-vector), vector.checked_neg());
+    assert_eq!(None, Vector::new(0, i64::MIN).checked_neg());
+}
+
+
+#[test]
+fn norms_and_rotation() {
+    let vector = Vector::new(-3, 4);
+    assert_eq!((7, 4), (vector.manhattan(), vector.chebyshev()));
+    assert_eq!(7, Point::new(1, 1).manhattan_distance(&Point::new(-2, 5)));
+    assert_eq!(4, Point::new(1, 1).chebyshev_distance(&Point::new(-2, 5)));
+
+    let extreme = Vector::new(i64::MIN, i64::MIN);
+    assert_eq!((None, Some(1 << 63)), (extreme.checked_manhattan(), extreme.checked_chebyshev()));
+    assert_eq!(Some(u64::MAX), Vector::new(i64::MIN, i64::MAX).checked_manhattan());
+    assert_eq!(Some(7), Point::new(1, 1).checked_manhattan_distance(&Point::new(-2, 5)));
+    assert_eq!(None, Point::new(i64::MIN, 0).checked_manhattan_distance(&Point::new(i64::MAX, 0)));
+    assert_eq!(None, Point::new(i64::MIN, 0).checked_chebyshev_distance(&Point::new(1, 0)));
+
+    // up turns into right, as with Move
+    let up = Vector::new(0, -1);
+    assert_eq!(Move::TOP.move_90(), Move::RIGHT);
+    assert_eq!(Vector::new(1, 0), up.rotate_right());
+    assert_eq!(Vector::new(-1, 0), up.rotate_left());
+    assert_eq!(-up, up.rotate_right().rotate_right());
+    assert_eq!(vector, vector.rotate_left().rotate_right());
+}
+
+
+#[test]
+fn positions() {
+    let grid = Grid::new(4, 3, '.');
+    assert_eq!(Some(Position { x: 3, y: 2 }), Point::new(3, 2).to_position(&grid));
+    assert_eq!(None, Point::new(-1, 2).to_position(&grid));
+    assert_eq!(None, Point::new(4, 2).to_position(&grid));
+    assert_eq!(Some(Point::new(1, 2)), Point::from_position(&Position { x: 1, y: 2 }, &grid));
+    assert_eq!(None, Point::from_position(&Position { x: 1, y: 3 }, &grid));
+
+    assert!(Position::try_from(Point::new(0, -1)).is_err());
+    assert!(Point::try_from(Position { x: usize::MAX, y: 0 }).is_err());
+}
+
+}
@@ -1,4 +1,3 @@
-use std::{fmt::write, path::Display};
 use super::Move;
 
 
@@ -68,6 +67,16 @@ impl Position {
         ]
    }
 
+   pub fn around(&self) -> Vec<Position> {
+        Move::all_sides().iter()
+                         .filter_map(|direction| self.move_to(direction))
+                         .collect()
+   }
+
+   pub fn manhattan_distance(&self, other: &Position) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+   }
+
    pub fn distance_to(&self, other: &Position) -> (i32, i32) {
         let diff_x: i32 = other.x as i32 - self.x as i32;
         let diff_y: i32 = other.y as i32 - self.y as i32;
@@ -0,0 +1,324 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::grid::Grid;
+use crate::point::{Point, Vector};
+use crate::position::Position;
+use crate::Move;
+
+
+/// Which neighbours of a cell belong to the same region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    /// Only the direct sides.
+    FOUR,
+    /// The direct sides and the diagonals.
+    EIGHT,
+}
+
+
+impl Connectivity {
+
+    fn directions(&self) -> &'static [Move] {
+        const FOUR: [Move; 4] = [Move::TOP, Move::RIGHT, Move::DOWN, Move::LEFT];
+        const EIGHT: [Move; 8] = [Move::TOP, Move::TOP_RIGHT, Move::RIGHT, Move::DOWN_RIGHT,
+                                  Move::DOWN, Move::DOWN_LEFT, Move::LEFT, Move::TOP_LEFT];
+        match self {
+            Connectivity::FOUR => &FOUR,
+            Connectivity::EIGHT => &EIGHT,
+        }
+    }
+
+    /// The connectivity of the cells around a region: a region that connects over its diagonals
+    /// closes off the cells around it along the diagonals, and the other way around.
+    pub fn dual(&self) -> Connectivity {
+        match self {
+            Connectivity::FOUR => Connectivity::EIGHT,
+            Connectivity::EIGHT => Connectivity::FOUR,
+        }
+    }
+
+}
+
+
+/// Connected cells of a grid.
+#[derive(Debug, Clone)]
+pub struct Region {
+    start: Position,
+    cells: HashSet<Position>,
+    connectivity: Connectivity,
+}
+
+
+/// Splits the grid into regions of neighbouring cells with equal values.
+pub fn regions<T: PartialEq>(grid: &Grid<T>, connectivity: Connectivity) -> Vec<Region> {
+    regions_by(grid, connectivity, |a, b| a == b)
+}
+
+
+/// The regions of cells that match the predicate; the other cells are in none.
+pub fn regions_where<T, P>(grid: &Grid<T>, connectivity: Connectivity, predicate: P) -> Vec<Region>
+    where P: Fn(&T) -> bool
+{
+    let matching: Vec<Position> = grid.find_all_positions(|value| predicate(value));
+    flood(grid.width, grid.height, matching, connectivity, |_, next| predicate(&grid[*next]))
+}
+
+
+/// Splits the grid into regions, where neighbours are joined when `joined` holds for their values.
+/// The regions are in reading order of their first cell.
+pub fn regions_by<T, F>(grid: &Grid<T>, connectivity: Connectivity, joined: F) -> Vec<Region>
+    where F: Fn(&T, &T) -> bool
+{
+    flood(grid.width, grid.height, grid.iter_lr_down().collect(), connectivity,
+          |from, next| joined(&grid[*from], &grid[*next]))
+}
+
+
+// fills a region from every start that is in none yet, stepping only within width x height
+fn flood<F>(width: usize, height: usize, starts: Vec<Position>, connectivity: Connectivity, joined: F) -> Vec<Region>
+    where F: Fn(&Position, &Position) -> bool
+{
+    let mut visited = vec![false; width * height];
+    let mut regions = Vec::new();
+    for start in starts {
+        if visited[start.y * width + start.x] {
+            continue;
+        }
+        visited[start.y * width + start.x] = true;
+        let mut cells = HashSet::from([start]);
+        let mut todo = vec![start];
+        while let Some(position) = todo.pop() {
+            for direction in connectivity.directions() {
+                let Some(next) = position.move_to(direction).filter(|p| p.x < width && p.y < height) else {
+                    continue;
+                };
+                if !visited[next.y * width + next.x] && joined(&position, &next) {
+                    visited[next.y * width + next.x] = true;
+                    cells.insert(next);
+                    todo.push(next);
+                }
+            }
+        }
+        regions.push(Region { start, cells, connectivity });
+    }
+    regions
+}
+
+
+// the clockwise edge along a side of a cell, as its start corner and direction
+fn edge(cell: &Position, side: &Move) -> (Point, Vector) {
+    let (x, y) = (cell.x as i64, cell.y as i64);
+    match side {
+        Move::TOP => (Point::new(x, y), Vector::new(1, 0)),
+        Move::RIGHT => (Point::new(x + 1, y), Vector::new(0, 1)),
+        Move::DOWN => (Point::new(x + 1, y + 1), Vector::new(-1, 0)),
+        _ => (Point::new(x, y + 1), Vector::new(0, -1)),
+    }
+}
+
+
+impl Region {
+
+    /// The first cell of the region in reading order of the grid it was found in.
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &Position> {
+        self.cells.iter()
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        self.cells.contains(position)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn has(&self, position: &Position, direction: &Move) -> bool {
+        position.move_to(direction).is_some_and(|p| self.cells.contains(&p))
+    }
+
+    /// The number of cell sides that do not touch another cell of the region, holes included.
+    pub fn perimeter(&self) -> usize {
+        self.cells.iter()
+                  .map(|cell| Move::direct_sides().iter().filter(|side| !self.has(cell, side)).count())
+                  .sum()
+    }
+
+    /// The number of straight sides of the fence, holes included; it equals the number of corners.
+    pub fn sides(&self) -> usize {
+        const CORNERS: [(Move, Move, Move); 4] = [
+            (Move::TOP, Move::RIGHT, Move::TOP_RIGHT),
+            (Move::RIGHT, Move::DOWN, Move::DOWN_RIGHT),
+            (Move::DOWN, Move::LEFT, Move::DOWN_LEFT),
+            (Move::LEFT, Move::TOP, Move::TOP_LEFT),
+        ];
+        self.cells.iter()
+                  .map(|cell| CORNERS.iter().filter(|(a, b, diagonal)| {
+                      let (a, b) = (self.has(cell, a), self.has(cell, b));
+                      // an outer corner, or an inner corner where the diagonal cell is missing
+                      (!a && !b) || (a && b && !self.has(cell, diagonal))
+                  }).count())
+                  .sum()
+    }
+
+    /// The top left and bottom right cell of the smallest rectangle around the region.
+    pub fn bounding_box(&self) -> (Position, Position) {
+        let (mut min, mut max) = (self.start, self.start);
+        for cell in self.cells.iter() {
+            min = Position { x: min.x.min(cell.x), y: min.y.min(cell.y) };
+            max = Position { x: max.x.max(cell.x), y: max.y.max(cell.y) };
+        }
+        (min, max)
+    }
+
+    /// The regions of cells that are enclosed by this region, whatever their values.
+    pub fn holes(&self) -> Vec<Region> {
+        let (min, max) = self.bounding_box();
+        let (width, height) = (max.x - min.x + 1, max.y - min.y + 1);
+        let others: Vec<Position> = (0..width * height).map(|idx| Position::from_width(idx, width))
+                                                       .filter(|cell| !self.cells.contains(&(*cell + min)))
+                                                       .collect();
+        let on_border = |cell: &Position| cell.x == 0 || cell.y == 0 || cell.x == width - 1 || cell.y == height - 1;
+
+        // whatever reaches the border of the box is outside the region
+        flood(width, height, others, self.connectivity.dual(), |_, next| !self.cells.contains(&(*next + min)))
+            .into_iter()
+            .filter(|hole| !hole.cells.iter().any(on_border))
+            .map(|hole| Region {
+                start: hole.start + min,
+                cells: hole.cells.iter().map(|cell| *cell + min).collect(),
+                connectivity: hole.connectivity,
+            })
+            .collect()
+    }
+
+    /// The corners of the outer fence, clockwise from the top left corner of the start cell.
+    /// Corners are the grid lines between cells, so cell (x, y) spans (x, y) to (x + 1, y + 1).
+    pub fn outline(&self) -> Vec<Point> {
+        let mut edges: HashMap<Point, Vec<Vector>> = HashMap::new();
+        for cell in self.cells.iter() {
+            for side in Move::direct_sides().iter().filter(|side| !self.has(cell, side)) {
+                let (from, direction) = edge(cell, side);
+                edges.entry(from).or_default().push(direction);
+            }
+        }
+
+        // the start cell is the first in reading order, so its top edge is on the outer fence
+        let (start, east) = edge(&self.start, &Move::TOP);
+        let mut corners = vec![start];
+        let (mut at, mut direction) = (start + east, east);
+        loop {
+            let choices = &edges[&at];
+            let next = match choices[..] {
+                [only] => only,
+                // two cells of the region meet at this corner; separate ones keep to their own cell
+                _ => match self.connectivity {
+                    Connectivity::FOUR => direction.rotate_right(),
+                    Connectivity::EIGHT => direction.rotate_left(),
+                },
+            };
+            if at == start && next == east {
+                return corners;
+            }
+            if next != direction {
+                corners.push(at);
+            }
+            direction = next;
+            at += direction;
+        }
+    }
+
+}
+
+
+
+#[cfg(test)]
+pub mod regions_tests {
+    use super::*;
+    use std::str::FromStr;
+
+
+fn grid(input: &str) -> Grid<char> {
+    Grid::from_str(input).unwrap()
+}
+
+
+#[test]
+fn partition() {
+    let garden = grid("AAAA\nBBCD\nBBCC\nEEEC");
+    let regions = regions(&garden, Connectivity::FOUR);
+    let summary: Vec<(char, usize, usize, usize)> = regions.iter()
+        .map(|r| (garden[r.start()], r.area(), r.perimeter(), r.sides()))
+        .collect();
+    assert_eq!(vec![('A', 4, 10, 4), ('B', 4, 8, 4), ('C', 4, 10, 8), ('D', 1, 4, 4), ('E', 3, 8, 4)], summary);
+
+    // diagonal neighbours join under 8-connectivity
+    let diagonal = grid("X.X\n.X.\nX..");
+    assert_eq!(4, regions_where(&diagonal, Connectivity::FOUR, |c| *c == 'X').len());
+    let joined = regions_where(&diagonal, Connectivity::EIGHT, |c| *c == 'X');
+    assert_eq!(1, joined.len());
+    assert_eq!(4, joined[0].area());
+    assert_eq!(16, joined[0].perimeter());
+    assert_eq!(16, joined[0].sides());
+    assert_eq!((Position { x: 0, y: 0 }, Position { x: 2, y: 2 }), joined[0].bounding_box());
+
+    // joined by a custom rule: digits that differ by at most one
+    let heights: Grid<char> = grid("1239\n2398");
+    let slopes = regions_by(&heights, Connectivity::FOUR, |a, b| (*a as i32 - *b as i32).abs() <= 1);
+    assert_eq!(vec![5, 3], slopes.iter().map(|r| r.area()).collect::<Vec<_>>());
+}
+
+
+#[test]
+fn holes_and_sides() {
+    let garden = grid("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO");
+    let outer = &regions(&garden, Connectivity::FOUR)[0];
+    assert_eq!((21, 36, 20), (outer.area(), outer.perimeter(), outer.sides()));
+    let holes = outer.holes();
+    assert_eq!(4, holes.len());
+    assert!(holes.iter().all(|hole| hole.area() == 1 && garden[hole.start()] == 'X'));
+
+    // a gap in the ring lets the inside out
+    let open = grid("####\n#..#\n#...\n####");
+    let ring = &regions_where(&open, Connectivity::FOUR, |c| *c == '#')[0];
+    assert!(ring.holes().is_empty());
+    let closed = grid("####\n#..#\n#..#\n####");
+    let ring = &regions_where(&closed, Connectivity::FOUR, |c| *c == '#')[0];
+    assert_eq!(vec![4], ring.holes().iter().map(|h| h.area()).collect::<Vec<_>>());
+
+    // a diagonal ring only closes when its diagonals connect it
+    let diamond = grid(".#.\n#.#\n.#.");
+    let walls = regions_where(&diamond, Connectivity::EIGHT, |c| *c == '#');
+    assert_eq!(1, walls.len());
+    assert_eq!(1, walls[0].holes().len());
+    assert_eq!(Position { x: 1, y: 1 }, walls[0].holes()[0].start());
+}
+
+
+#[test]
+fn outline() {
+    let shape = grid("#..\n##.\n###");
+    let region = &regions_where(&shape, Connectivity::FOUR, |c| *c == '#')[0];
+    let corners = [(0, 0), (1, 0), (1, 1), (2, 1), (2, 2), (3, 2), (3, 3), (0, 3)];
+    assert_eq!(corners.map(|(x, y)| Point::new(x, y)).to_vec(), region.outline());
+
+    // a region that touches itself at a corner: around it, or through the corner when it connects there
+    let pinch = grid("##.\n#.#\n.##");
+    assert_eq!(2, regions_where(&pinch, Connectivity::FOUR, |c| *c == '#').len());
+    // the cells inside this loop touch the outside over the diagonals, so the fence runs between them
+    let looped = grid("###\n#.#\n##.\n#.#\n###");
+    let four = &regions_where(&looped, Connectivity::FOUR, |c| *c == '#')[0];
+    let corners = [(0, 0), (3, 0), (3, 2), (2, 2), (2, 1), (1, 1), (1, 2), (2, 2),
+                   (2, 3), (1, 3), (1, 4), (2, 4), (2, 3), (3, 3), (3, 5), (0, 5)];
+    assert_eq!(corners.map(|(x, y)| Point::new(x, y)).to_vec(), four.outline());
+    assert_eq!(corners.len(), four.sides());
+    assert!(four.holes().is_empty());
+    let eight = &regions_where(&pinch, Connectivity::EIGHT, |c| *c == '#')[0];
+    let corners = [(0, 0), (2, 0), (2, 1), (3, 1), (3, 3), (1, 3), (1, 2), (0, 2)];
+    assert_eq!(corners.map(|(x, y)| Point::new(x, y)).to_vec(), eight.outline());
+}
+
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::AddAssign;
+
+
+/// How often each value occurs, in no particular order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Multiset<V: Eq + Hash, C> {
+    counts: HashMap<V, C>,
+}
+
+
+impl<V: Eq + Hash, C: Copy + Default + AddAssign> Multiset<V, C> {
+
+    pub fn new() -> Self {
+        Multiset { counts: HashMap::new() }
+    }
+
+    pub fn add(&mut self, value: V, count: C) {
+        *self.counts.entry(value).or_default() += count;
+    }
+
+    /// How often the value occurs, the default count when not at all.
+    pub fn count(&self, value: &V) -> C {
+        self.counts.get(value).copied().unwrap_or_default()
+    }
+
+    /// The number of different values.
+    pub fn distinct(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// The number of values, counting every occurrence.
+    pub fn total(&self) -> C {
+        let mut total = C::default();
+        self.counts.values().for_each(|count| total += *count);
+        total
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&V, &C)> {
+        self.counts.iter()
+    }
+
+}
+
+
+impl<V: Eq + Hash, C: Copy + Default + AddAssign> Default for Multiset<V, C> {
+    fn default() -> Self {
+        Multiset::new()
+    }
+}
+
+
+impl<V: Eq + Hash, C: Copy + Default + AddAssign + From<u8>> FromIterator<V> for Multiset<V, C> {
+    fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> Self {
+        let mut multiset = Multiset::new();
+        iter.into_iter().for_each(|value| multiset.add(value, C::from(1)));
+        multiset
+    }
+}
+
+
+/// Replaces every value of a multiset by the values the rule gives for it, one step at a time.
+/// Equal values are rewritten together, and the rule is asked only once for each value.
+pub struct Rewriter<V, R> {
+    rule: R,
+    memo: HashMap<V, Vec<V>>,
+}
+
+
+impl<V, R, I> Rewriter<V, R>
+    where V: Clone + Eq + Hash,
+          R: FnMut(&V) -> I,
+          I: IntoIterator<Item = V>
+{
+
+    pub fn new(rule: R) -> Self {
+        Rewriter { rule, memo: HashMap::new() }
+    }
+
+    pub fn step<C>(&mut self, multiset: &Multiset<V, C>) -> Multiset<V, C>
+        where C: Copy + Default + AddAssign
+    {
+        let mut next = Multiset::new();
+        for (value, count) in multiset.iter() {
+            if !self.memo.contains_key(value) {
+                let rewritten = (self.rule)(value).into_iter().collect();
+                self.memo.insert(value.clone(), rewritten);
+            }
+            for rewritten in self.memo[value].iter() {
+                next.add(rewritten.clone(), *count);
+            }
+        }
+        next
+    }
+
+}
+
+
+/// The multiset after the rule has been applied `steps` times to the initial values.
+pub fn count_after<V, C, R, I>(steps: usize, initial: impl IntoIterator<Item = V>, rule: R) -> Multiset<V, C>
+    where V: Clone + Eq + Hash,
+          C: Copy + Default + AddAssign + From<u8>,
+          R: FnMut(&V) -> I,
+          I: IntoIterator<Item = V>
+{
+    let mut rewriter = Rewriter::new(rule);
+    (0..steps).fold(initial.into_iter().collect(), |multiset, _| rewriter.step(&multiset))
+}
+
+
+/// The multisets at the start and after each of the steps, `steps + 1` in all.
+pub fn multisets_after<V, C, R, I>(steps: usize, initial: impl IntoIterator<Item = V>, rule: R) -> Vec<Multiset<V, C>>
+    where V: Clone + Eq + Hash,
+          C: Copy + Default + AddAssign + From<u8>,
+          R: FnMut(&V) -> I,
+          I: IntoIterator<Item = V>
+{
+    let mut rewriter = Rewriter::new(rule);
+    let mut history = vec![initial.into_iter().collect::<Multiset<V, C>>()];
+    for _ in 0..steps {
+        let next = rewriter.step(history.last().unwrap());
+        history.push(next);
+    }
+    history
+}
+
+
+
+#[cfg(test)]
+pub mod rewrite_tests {
+    use super::*;
+
+
+// every letter grows: a becomes ab, b becomes a
+fn grow(letter: &char) -> Vec<char> {
+    match letter {
+        'a' => vec!['a', 'b'],
+        _ => vec!['a'],
+    }
+}
+
+
+#[test]
+fn fibonacci() {
+    let history: Vec<Multiset<char, u64>> = multisets_after(10, "a".chars(), grow);
+    let totals: Vec<u64> = history.iter().map(|m| m.total()).collect();
+    assert_eq!(vec![1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144], totals);
+    assert_eq!((89, 55), (history[10].count(&'a'), history[10].count(&'b')));
+    assert_eq!(0, history[0].count(&'b'));
+    assert_eq!(2, history[5].distinct());
+
+    let after: Multiset<char, u128> = count_after(150, "ab".chars(), grow);
+    assert_eq!(after.count(&'a') + after.count(&'b'), after.total());
+}
+
+
+#[test]
+fn memoised_rule() {
+    let mut calls = 0;
+    let multiset: Multiset<u32, u64> = count_after(20, [1, 1, 2], |n: &u32| {
+        calls += 1;
+        [n % 7 + 1, (n * 3) % 7 + 1]
+    });
+    assert_eq!(3 << 20, multiset.total());
+    // only 7 values ever occur, each is rewritten once
+    assert!(calls <= 7);
+    assert!(multiset.distinct() <= 7);
+}
+
+
+#[test]
+fn collapsed_values() {
+    let multiset: Multiset<&str, u64> = ["x", "y", "x"].into_iter().collect();
+    assert_eq!((2, 1, 3), (multiset.count(&"x"), multiset.count(&"y"), multiset.total()));
+    let mut rewriter = Rewriter::new(|value: &&str| if *value == "x" { vec!["y", "y"] } else { vec![] });
+    let next = rewriter.step(&multiset);
+    assert_eq!((0, 4, 1), (next.count(&"x"), next.count(&"y"), next.distinct()));
+    assert_eq!(Multiset::<&str, u64>::new(), rewriter.step(&next));
+}
+
+}
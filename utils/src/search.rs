@@ -0,0 +1,302 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::grid::Grid;
+use crate::position::Position;
+use crate::Move;
+
+
+/// The outcome of a search: the cheapest cost to every visited state, and for each state
+/// all predecessors that reach it at that cost.
+#[derive(Debug)]
+pub struct Paths<S> {
+    costs: HashMap<S, u64>,
+    predecessors: HashMap<S, Vec<S>>,
+    goals: Vec<S>,
+}
+
+
+/// A state in a grid: the position and the direction it is facing.
+pub type Heading = (Position, Move);
+
+
+impl<S: Clone + Eq + Hash> Paths<S> {
+
+    /// The cost of the cheapest path to a goal.
+    pub fn cost(&self) -> Option<u64> {
+        self.goals.first().and_then(|goal| self.cost_to(goal))
+    }
+
+    /// All goal states reached at the cheapest cost.
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    pub fn cost_to(&self, state: &S) -> Option<u64> {
+        self.costs.get(state).copied()
+    }
+
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map(|p| p.as_slice()).unwrap_or_default()
+    }
+
+    /// One cheapest path from the start to the first goal, both included.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goals.first()?)
+    }
+
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.costs.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(previous) = self.predecessors(path.last()?).first() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every state that lies on at least one cheapest path to a goal.
+    pub fn on_optimal_paths(&self) -> HashSet<S> {
+        let mut seen: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut todo = self.goals.clone();
+        while let Some(state) = todo.pop() {
+            for previous in self.predecessors(&state) {
+                if seen.insert(previous.clone()) {
+                    todo.push(previous.clone());
+                }
+            }
+        }
+        seen
+    }
+
+}
+
+
+// Dijkstra ordered by cost + heuristic; with a heuristic of 0 this is plain Dijkstra.
+// The search goes on until the cheapest goal cost is exceeded, so that every
+// goal and predecessor of equal cost is recorded. Without a goal it visits all reachable states.
+fn search<S, N, I, H, G>(start: S, mut neighbours: N, heuristic: H, mut is_goal: G) -> Paths<S>
+    where S: Clone + Eq + Hash,
+          N: FnMut(&S) -> I,
+          I: IntoIterator<Item = (S, u64)>,
+          H: Fn(&S) -> u64,
+          G: FnMut(&S) -> bool,
+{
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut predecessors: HashMap<S, Vec<S>> = HashMap::new();
+    let mut goals = Vec::new();
+    let mut best = None;
+
+    // the heap holds indexes into `states`, so that states need no ordering
+    let mut states = vec![start.clone()];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+
+    while let Some(Reverse((priority, cost, index))) = queue.pop() {
+        if best.is_some_and(|best| priority > best) {
+            break;
+        }
+        let state = states[index].clone();
+        if costs[&state] < cost {
+            continue;
+        }
+        if is_goal(&state) {
+            best = Some(cost);
+            goals.push(state);
+            continue;
+        }
+
+        for (next, step) in neighbours(&state) {
+            if next == start {
+                continue;
+            }
+            let next_cost = cost + step;
+            match costs.get(&next) {
+                Some(&known) if known < next_cost => {},
+                Some(&known) if known == next_cost => {
+                    let previous = predecessors.entry(next).or_default();
+                    if !previous.contains(&state) {
+                        previous.push(state.clone());
+                    }
+                },
+                _ => {
+                    costs.insert(next.clone(), next_cost);
+                    predecessors.insert(next.clone(), vec![state.clone()]);
+                    queue.push(Reverse((next_cost + heuristic(&next), next_cost, states.len())));
+                    states.push(next);
+                },
+            }
+        }
+    }
+
+    Paths { costs, predecessors, goals }
+}
+
+
+/// Breadth-first search: every step costs 1.
+pub fn bfs<S, N, I, G>(start: S, mut neighbours: N, is_goal: G) -> Paths<S>
+    where S: Clone + Eq + Hash,
+          N: FnMut(&S) -> I,
+          I: IntoIterator<Item = S>,
+          G: FnMut(&S) -> bool,
+{
+    search(start, |state| neighbours(state).into_iter().map(|next| (next, 1)), |_| 0, is_goal)
+}
+
+
+pub fn dijkstra<S, N, I, G>(start: S, neighbours: N, is_goal: G) -> Paths<S>
+    where S: Clone + Eq + Hash,
+          N: FnMut(&S) -> I,
+          I: IntoIterator<Item = (S, u64)>,
+          G: FnMut(&S) -> bool,
+{
+    search(start, neighbours, |_| 0, is_goal)
+}
+
+
+/// A* search; the heuristic must never overestimate the remaining cost and must be consistent.
+pub fn astar<S, N, I, H, G>(start: S, neighbours: N, heuristic: H, is_goal: G) -> Paths<S>
+    where S: Clone + Eq + Hash,
+          N: FnMut(&S) -> I,
+          I: IntoIterator<Item = (S, u64)>,
+          H: Fn(&S) -> u64,
+          G: FnMut(&S) -> bool,
+{
+    search(start, neighbours, heuristic, is_goal)
+}
+
+
+/// The direct sides of a position that are in the grid and passable.
+pub fn grid_neighbours<T, P>(grid: &Grid<T>, position: &Position, passable: P) -> Vec<Position>
+    where P: Fn(&T) -> bool
+{
+    position.sides().into_iter()
+                    .flatten()
+                    .filter(|side| grid.at(side).is_some_and(&passable))
+                    .collect()
+}
+
+
+pub fn grid_bfs<T, P>(grid: &Grid<T>, start: Position, goal: Position, passable: P) -> Paths<Position>
+    where P: Fn(&T) -> bool
+{
+    bfs(start, |position| grid_neighbours(grid, position, &passable), |position| *position == goal)
+}
+
+
+pub fn grid_astar<T, P>(grid: &Grid<T>, start: Position, goal: Position, passable: P) -> Paths<Position>
+    where P: Fn(&T) -> bool
+{
+    astar(start,
+          |position| grid_neighbours(grid, position, &passable).into_iter().map(|next| (next, 1)),
+          |position| position.manhattan_distance(&goal) as u64,
+          |position| *position == goal)
+}
+
+
+/// Dijkstra over headings: a step forward costs `step_cost`, turning 90 degrees on the spot costs `turn_cost`.
+pub fn grid_dijkstra_heading<T, P>(grid: &Grid<T>, start: Heading, goal: Position, passable: P, step_cost: u64, turn_cost: u64) -> Paths<Heading>
+    where P: Fn(&T) -> bool
+{
+    dijkstra(start,
+             |(position, direction)| {
+                 let mut next = vec![
+                     ((*position, direction.turn_left()), turn_cost),
+                     ((*position, direction.move_90()), turn_cost),
+                 ];
+                 if let Some(forward) = position.move_to(direction).filter(|p| grid.at(p).is_some_and(&passable)) {
+                     next.push(((forward, *direction), step_cost));
+                 }
+                 next
+             },
+             |(position, _)| *position == goal)
+}
+
+
+
+#[cfg(test)]
+pub mod search_tests {
+    use super::*;
+    use std::str::FromStr;
+
+const MAZE: &str =
+"###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
+
+
+#[test]
+fn dijkstra_graph() {
+    // a -1-> b -1-> d, a -2-> c -0-> d, a -5-> d
+    let edges = HashMap::from([
+        ('a', vec![('b', 1), ('c', 2), ('d', 5)]),
+        ('b', vec![('d', 1)]),
+        ('c', vec![('d', 0)]),
+    ]);
+    let paths = dijkstra('a', |node| edges.get(node).cloned().unwrap_or_default(), |node| *node == 'd');
+    assert_eq!(Some(2), paths.cost());
+    assert_eq!(&['b', 'c'], paths.predecessors(&'d'));
+    assert_eq!(Some(vec!['a', 'b', 'd']), paths.path());
+    assert_eq!(HashSet::from(['a', 'b', 'c', 'd']), paths.on_optimal_paths());
+}
+
+
+#[test]
+fn zero_cost_cycle() {
+    let paths = dijkstra(0, |n| vec![((n + 1) % 3, 0)], |n| *n == 2);
+    assert_eq!(Some(0), paths.cost());
+    assert_eq!(Some(vec![0, 1, 2]), paths.path());
+}
+
+
+#[test]
+fn unreachable() {
+    let paths = bfs(0, |n| if *n < 3 { vec![n + 1] } else { vec![] }, |n| *n == 5);
+    assert_eq!(None, paths.cost());
+    assert_eq!(None, paths.path());
+    assert_eq!(Some(3), paths.cost_to(&3));
+    assert!(paths.on_optimal_paths().is_empty());
+}
+
+
+#[test]
+fn grid_bfs_and_astar() {
+    let maze: Grid<char> = Grid::from_str(MAZE).unwrap();
+    let start = maze.find_all_positions(|c| *c == 'S')[0];
+    let end = maze.find_all_positions(|c| *c == 'E')[0];
+
+    let paths = grid_bfs(&maze, start, end, |c| *c != '#');
+    let path = paths.path().unwrap();
+    assert_eq!(Some(path.len() as u64 - 1), paths.cost());
+    assert_eq!((start, end), (path[0], *path.last().unwrap()));
+    assert!(path.windows(2).all(|step| step[0].manhattan_distance(&step[1]) == 1));
+
+    assert_eq!(paths.cost(), grid_astar(&maze, start, end, |c| *c != '#').cost());
+}
+
+
+#[test]
+fn grid_heading() {
+    let maze: Grid<char> = Grid::from_str(MAZE).unwrap();
+    let start = maze.find_all_positions(|c| *c == 'S')[0];
+    let end = maze.find_all_positions(|c| *c == 'E')[0];
+
+    let paths = grid_dijkstra_heading(&maze, (start, Move::RIGHT), end, |c| *c != '#', 1, 1000);
+    assert_eq!(Some(7036), paths.cost());
+    let tiles: HashSet<Position> = paths.on_optimal_paths().iter().map(|(position, _)| *position).collect();
+    assert_eq!(45, tiles.len());
+}
+
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+
+/// A puzzle of one day: parse the input once, then solve both parts on the parsed input.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+    const INPUT: &'static str;
+
+    type Input: 'static;
+
+    fn parse(input: &str) -> Result<Self::Input, String>;
+
+    fn part1(input: &Self::Input) -> Result<impl Display, String>;
+
+    fn part2(input: &Self::Input) -> Result<impl Display, String>;
+}
+
+
+pub fn not_solved() -> Result<String, String> {
+    Err(String::from("not solved"))
+}
+
+
+/// The parsed input of a registered puzzle, with the answer type erased to a string.
+pub trait Parsed {
+    fn solve(&self, part: u8) -> Result<String, String>;
+}
+
+
+struct ParsedInput<S: Solution>(S::Input);
+
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn solve(&self, part: u8) -> Result<String, String> {
+        match part {
+            1 => S::part1(&self.0).map(|answer| answer.to_string()),
+            2 => S::part2(&self.0).map(|answer| answer.to_string()),
+            _ => Err(format!("unknown part {}", part)),
+        }
+    }
+}
+
+
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+    pub input: &'static str,
+    parse: fn(&str) -> Result<Box<dyn Parsed>, String>,
+}
+
+
+impl Puzzle {
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, String> {
+        (self.parse)(input)
+    }
+}
+
+
+fn parse_boxed<S: Solution + 'static>(input: &str) -> Result<Box<dyn Parsed>, String> {
+    let parsed = S::parse(input)?;
+    Ok(Box::new(ParsedInput::<S>(parsed)))
+}
+
+
+#[derive(Default)]
+pub struct Registry {
+    puzzles: BTreeMap<(u16, u8), Puzzle>,
+}
+
+
+impl Registry {
+
+    pub fn register<S: Solution + 'static>(&mut self) {
+        self.puzzles.insert((S::YEAR, S::DAY), Puzzle {
+            year: S::YEAR,
+            day: S::DAY,
+            input: S::INPUT,
+            parse: parse_boxed::<S>,
+        });
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&Puzzle> {
+        self.puzzles.get(&(year, day))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Puzzle> {
+        self.puzzles.values()
+    }
+
+}